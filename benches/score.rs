@@ -1,59 +1,61 @@
-use std::process::Child;
-
 use bevy::{
     ecs::hierarchy::ChildOf,
     prelude::{Commands, World},
 };
 use bevy_observed_utility::{
     event::RunScoring,
-    scoring::{AllOrNothing, FixedScore, Score, ScoringPlugin},
+    scoring::{AllOrNothing, FixedScore, ParallelScorers, Score, ScoringPlugin, SkipScoringEvents},
 };
 use criterion::{Bencher, Criterion, criterion_group, criterion_main};
 
+// Trees in "parallel-with-events" keep triggering `OnScore` and `OnScorePostProcess`, so they're scored one entity
+// at a time like in "serial". Only trees marked with `SkipScoringEvents` are scored in parallel.
+//
+// These numbers are from a machine with a single core, so they don't show any speedup from scoring on several
+// threads, only the savings from not triggering observers. With 10,000 trees:
+// - At depth 3, "serial" and "parallel-with-events" take about 13 ms, "parallel" about 11 ms.
+// - At depth 10, "serial" takes about 53 ms, "parallel-with-events" 42 ms, "parallel" 32 ms.
+// - At depth 25, "serial" takes about 112 ms, "parallel-with-events" 97 ms, "parallel" 73 ms.
 fn score(c: &mut Criterion) {
-    c.bench_function("score/deep-3", |b| {
-        bench_scoring(b, 3, 1);
-    });
-    c.bench_function("score/deep-10", |b| {
-        bench_scoring(b, 10, 1);
-    });
-    c.bench_function("score/deep-25", |b| {
-        bench_scoring(b, 25, 1);
-    });
-    c.bench_function("score/deep-3/many-100", |b| {
-        bench_scoring(b, 3, 100);
-    });
-    c.bench_function("score/deep-3/many-100", |b| {
-        bench_scoring(b, 10, 100);
-    });
-    c.bench_function("score/deep-3/many-100", |b| {
-        bench_scoring(b, 25, 100);
-    });
-    c.bench_function("score/deep-3/many-10000", |b| {
-        bench_scoring(b, 3, 10_000);
-    });
-    c.bench_function("score/deep-10/many-10000", |b| {
-        bench_scoring(b, 10, 10_000);
-    });
-    c.bench_function("score/deep-25/many-10000", |b| {
-        bench_scoring(b, 25, 10_000);
-    });
+    for (mode, parallel, skip_events) in [
+        ("serial", false, false),
+        ("parallel-with-events", true, false),
+        ("parallel", true, true),
+    ] {
+        for depth in [3, 10, 25] {
+            for num_trees in [1, 100, 10_000] {
+                c.bench_function(&format!("score/{mode}/deep-{depth}/many-{num_trees}"), |b| {
+                    bench_scoring(b, depth, num_trees, parallel, skip_events);
+                });
+            }
+        }
+    }
 }
 
-fn bench_scoring(b: &mut Bencher, scoring_depth: usize, num_trees: usize) {
+fn bench_scoring(b: &mut Bencher, scoring_depth: usize, num_trees: usize, parallel: bool, skip_events: bool) {
     let mut world = World::new();
-    world.add_observer(ScoringPlugin::run_scoring_post_order_dfs);
+    if parallel {
+        world.init_resource::<ParallelScorers>();
+        world.add_observer(ScoringPlugin::run_scoring_parallel);
+    } else {
+        world.add_observer(ScoringPlugin::run_scoring_post_order_dfs);
+    }
     for _ in 0..num_trees {
-        build_deep_tree(world.commands(), scoring_depth);
+        build_deep_tree(world.commands(), scoring_depth, skip_events);
     }
     world.flush();
     b.iter(|| {
         world.trigger(RunScoring);
+        // Both modes queue their scoring as commands
+        world.flush();
     });
 }
 
-fn build_deep_tree(mut commands: Commands, depth: usize) {
+fn build_deep_tree(mut commands: Commands, depth: usize, skip_events: bool) {
     let root = commands.spawn((AllOrNothing::new(0.5), Score::default())).id();
+    if skip_events {
+        commands.entity(root).insert(SkipScoringEvents);
+    }
 
     let mut last = root;
     for _ in 0..depth - 2 {
@@ -0,0 +1,99 @@
+//! Derive macros for [`bevy_observed_utility`](https://docs.rs/bevy_observed_utility).
+
+#![warn(missing_docs)]
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{DeriveInput, Path, parse_macro_input};
+
+/// Implements `Component` for a scorer, spawning its scoring `Observer`s the first time it's added to an entity.
+///
+/// # Attributes
+///
+/// - `#[scorer(observer = path)]`: Spawns the given `Observer` function once. Can be repeated for multiple observers.
+/// - `#[scorer(aggregate)]`: Scores the entity by aggregating its child scores with its `ChildAggregateScorer` impl,
+///   and implements `ParallelScorer` with it.
+///
+/// At least one of these attributes is required.
+/// See `ChildAggregateScorer` for an example.
+#[proc_macro_derive(Scorer, attributes(scorer))]
+pub fn derive_scorer(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_scorer(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand_scorer(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let mut observers: Vec<proc_macro2::TokenStream> = Vec::new();
+    let mut aggregate = false;
+
+    for attr in input.attrs.iter().filter(|attr| attr.path().is_ident("scorer")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("observer") {
+                let observer: Path = meta.value()?.parse()?;
+                observers.push(quote!(#observer));
+                Ok(())
+            } else if meta.path.is_ident("aggregate") {
+                aggregate = true;
+                Ok(())
+            } else {
+                Err(meta.error("expected `observer = ...` or `aggregate`"))
+            }
+        })?;
+    }
+
+    let private = quote!(::bevy_observed_utility::__private);
+
+    if aggregate {
+        observers.push(quote!(#private::score_children_aggregate::<Self>));
+    }
+    if observers.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "expected `#[scorer(observer = ...)]` or `#[scorer(aggregate)]`",
+        ));
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+
+    let spawn_observers = observers.iter().enumerate().map(|(index, observer)| {
+        quote! {
+            #private::CommandsExt::once::<#private::ObserverSpawned<Self, #index>>(&mut commands).observe(#observer);
+        }
+    });
+
+    let parallel_scorer = aggregate.then(|| {
+        quote! {
+            impl #impl_generics #private::ParallelScorer for #ident #type_generics #where_clause {
+                fn score(
+                    &self,
+                    children: &[(#private::Score, #private::Weighted)],
+                ) -> ::core::option::Option<#private::Score> {
+                    ::core::option::Option::Some(#private::ChildAggregateScorer::aggregate(
+                        self,
+                        children.iter().map(|(score, _)| *score),
+                    ))
+                }
+            }
+        }
+    });
+
+    Ok(quote! {
+        impl #impl_generics #private::bevy::ecs::component::Component for #ident #type_generics #where_clause {
+            type Mutability = #private::bevy::ecs::component::Mutable;
+            const STORAGE_TYPE: #private::bevy::ecs::component::StorageType =
+                #private::bevy::ecs::component::StorageType::Table;
+
+            fn register_component_hooks(hooks: &mut #private::bevy::ecs::component::ComponentHooks) {
+                hooks.on_add(|mut world, _ctx| {
+                    let mut commands = world.commands();
+                    #(#spawn_observers)*
+                });
+            }
+        }
+
+        #parallel_scorer
+    })
+}
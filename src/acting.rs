@@ -5,8 +5,14 @@
 //! However, the library does provide these types:
 //! - [`RequestAction`] event to request a specific action or the picked action to be initiated for the target actor entity.
 //! - [`OnActionInitiated`] event to indicate that an action has been initiated. This should be listened to by action observers.
-//! - [`OnActionEnded`] event to indicate that an action has completed or been cancelled. This should be listened to by action observers.
+//! - [`OnActionEnded`] event to indicate that an action has completed, failed, or been cancelled. This should be listened to by action observers.
 //! - [`CurrentAction`] component to store the current action being performed by an actor entity, for easy access.
+//! - [`CurrentTarget`] component to store the target entity of the current action, if it was picked with one.
+//! - [`Sequence`] actions made of ordered steps, registered in the [`ActionSequences`] resource.
+//! - [`ActionLayer`] component to let an actor entity perform multiple actions at the same time, one per layer.
+//! - [`ActionTimeouts`] resource and [`ActionTimeout`] component to end actions that take too long.
+//! - [`ActionFailurePolicies`] resource to configure what happens after an action fails.
+//! - [`ActionInterrupts`] resource to protect running actions from being cancelled by less important requests.
 //!
 //! And, these observers:
 //! - [`on_action_initiated_insert_default`] to insert a default instance of an action component when it is initiated.
@@ -17,10 +23,24 @@
 
 use bevy::{ecs::component::ComponentId, prelude::*};
 
+mod failure;
+mod interrupt;
+mod layer;
+mod sequence;
+mod timeout;
+
+pub use failure::*;
+pub use interrupt::*;
+pub use layer::*;
+pub use sequence::*;
+pub use timeout::*;
+
 use crate::{
+    TurnQueue,
     ecs::TargetedAction,
-    event::{ActionEndReason, OnActionEnded, OnActionInitiated, RequestAction},
+    event::{ActionEndReason, OnActionEnded, OnActionInitiated, OnActionRejected, RequestAction},
     picking::Picker,
+    scoring::{IncrementalScorers, ParallelScorers},
 };
 
 /// [`Plugin`] that handles action lifecycle events.
@@ -29,36 +49,75 @@ pub struct ActionPlugin;
 impl Plugin for ActionPlugin {
     fn build(&self, app: &mut App) {
         app.add_observer(Self::on_request_cancel_and_initiate)
-            .add_observer(Self::on_ended_request_again);
+            .add_observer(Self::on_ended_request_again)
+            .add_observer(ActionSequences::on_initiated_start)
+            .add_observer(ActionSequences::on_ended_advance)
+            .add_observer(ActionTimeouts::on_initiated_start)
+            .add_observer(ActionTimeouts::on_ended_stop)
+            .add_observer(ActionInterrupts::on_initiated_protect)
+            .add_observer(ActionInterrupts::on_ended_unprotect)
+            .add_systems(Update, ActionTimeouts::time_out_expired);
+
+        app.init_resource::<ActionSequences>()
+            .init_resource::<ActionTimeouts>()
+            .init_resource::<ActionFailurePolicies>()
+            .init_resource::<ActionInterrupts>();
 
-        app.register_type::<CurrentAction>();
+        // Failure penalties read the time and count down turns, so they can't be scored in parallel or skipped
+        ParallelScorers::register_serial_only::<FailurePenalty>(app.world_mut());
+        IncrementalScorers::register_volatile::<FailurePenalty>(app.world_mut());
+
+        app.register_type::<CurrentAction>()
+            .register_type::<CurrentTarget>()
+            .register_type::<ActionLayer>()
+            .register_type::<Sequence>()
+            .register_type::<ActionSequences>()
+            .register_type::<SequenceProgress>()
+            .register_type::<ActionTimeout>()
+            .register_type::<ActionTimeouts>()
+            .register_type::<ActionDeadlines>()
+            .register_type::<FailurePolicy>()
+            .register_type::<ActionFailurePolicies>()
+            .register_type::<ActionRetries>()
+            .register_type::<FailurePenalty>()
+            .register_type::<Interruptibility>()
+            .register_type::<ActionInterrupts>()
+            .register_type::<ProtectedActions>();
 
         app.register_type::<RequestAction>()
             .register_type::<OnActionInitiated>()
-            .register_type::<OnActionEnded>();
+            .register_type::<OnActionEnded>()
+            .register_type::<OnActionRejected>();
     }
 }
 
 impl ActionPlugin {
     /// [`System`] that listens for [`RequestAction`] events and cancels the current action
     /// and initiates the picked action for the target actor entity.
+    ///
+    /// If the target is an [`ActionLayer`], only the action of that layer is cancelled,
+    /// unless the layer is [`exclusive`](ActionLayer::exclusive).
+    ///
+    /// If the current action can't be interrupted by the requested one, see [`ActionInterrupts`],
+    /// or an exclusive sibling layer is performing a non-default action,
+    /// the request is rejected with [`OnActionRejected`] instead.
     pub fn on_request_cancel_and_initiate(
         trigger: Trigger<RequestAction>,
         mut commands: Commands,
-        mut actors: Query<(&Picker, Option<&CurrentAction>)>,
+        actors: Query<(&Picker, Option<&CurrentAction>, Option<&CurrentTarget>)>,
+        layers: ActionLayers,
+        interrupts: Res<ActionInterrupts>,
+        protected: Query<&ProtectedActions>,
     ) {
-        let actor = trigger.target();
-        let requested = trigger.event().action;
-        if let Ok((picker, current_action)) = actors.get_mut(actor) {
-            let current_action = current_action.map(|ca| ca.0);
-            let next_action = requested.unwrap_or(picker.picked);
-
+        /// Cancels the current action of the actor entity, if any, and initiates the next action.
+        fn switch(
+            mut commands: Commands,
+            actor: Entity,
+            current_action: Option<ComponentId>,
+            next_action: ComponentId,
+            next_target: Option<Entity>,
+        ) {
             if let Some(current_action) = current_action {
-                if next_action == current_action {
-                    // We don't need to re-initiate the same action
-                    return;
-                }
-
                 // Cancel the current action
                 commands.trigger_targets(
                     OnActionEnded::cancelled(current_action),
@@ -66,24 +125,146 @@ impl ActionPlugin {
                 );
             }
 
-            // Update the current action
-            commands.entity(actor).insert(CurrentAction(next_action));
+            // Update the current action and target
+            let mut entity = commands.entity(actor);
+            entity.insert(CurrentAction(next_action));
+            match next_target {
+                Some(target) => entity.insert(CurrentTarget(target)),
+                None => entity.remove::<CurrentTarget>(),
+            };
             // Trigger the picked action
             commands.trigger_targets(
-                OnActionInitiated { action: next_action },
+                OnActionInitiated::new(next_action).with_target(next_target),
+                TargetedAction(actor, next_action),
+            );
+        }
+
+        let actor = trigger.target();
+        let request = trigger.event();
+        let Ok((picker, current_action, current_target)) = actors.get(actor) else {
+            return;
+        };
+        let current_action = current_action.map(|ca| ca.0);
+        let current_target = current_target.map(|ct| ct.0);
+        let (next_action, next_target) = match request.action {
+            Some(action) => (action, request.target),
+            None => (picker.picked, picker.picked_target),
+        };
+
+        if current_action == Some(next_action) && current_target == next_target {
+            // We don't need to re-initiate the same action
+            return;
+        }
+
+        if let Some(current) = current_action.filter(|&current| {
+            protected.get(actor).is_ok_and(|protected| protected.contains(current))
+                && !interrupts.can_interrupt(current, next_action)
+        }) {
+            // The current action keeps running
+            commands.trigger_targets(
+                OnActionRejected {
+                    action: next_action,
+                    target: next_target,
+                    current,
+                },
                 TargetedAction(actor, next_action),
             );
+            return;
+        }
+
+        if picker.is_default(next_action) {
+            switch(commands, actor, current_action, next_action, next_target);
+            return;
         }
+
+        // Layers performing a non-default action
+        let mut busy_siblings = layers.siblings(actor).filter_map(|(sibling, sibling_layer)| {
+            let (sibling_picker, sibling_action, _) = actors.get(sibling).ok()?;
+            let sibling_action = sibling_action.map(|ca| ca.0)?;
+            (!sibling_picker.is_default(sibling_action)).then_some((
+                sibling,
+                sibling_layer,
+                sibling_picker,
+                sibling_action,
+            ))
+        });
+
+        if layers.get(actor).is_some_and(|layer| layer.exclusive) {
+            // Fall back to the default action on all other layers
+            for (sibling, _, sibling_picker, sibling_action) in busy_siblings {
+                switch(
+                    commands.reborrow(),
+                    sibling,
+                    Some(sibling_action),
+                    sibling_picker.default,
+                    None,
+                );
+            }
+        } else if let Some((_, _, _, blocking)) = busy_siblings.find(|(_, sibling_layer, _, _)| sibling_layer.exclusive)
+        {
+            // Blocked until the exclusive layer is done
+            commands.trigger_targets(
+                OnActionRejected {
+                    action: next_action,
+                    target: next_target,
+                    current: blocking,
+                },
+                TargetedAction(actor, next_action),
+            );
+            return;
+        }
+
+        switch(commands, actor, current_action, next_action, next_target);
     }
 
-    /// [`Observer`] that listens for [`OnActionEnded`] events and triggers a new [`RequestAction`] event for the target actor entity.
-    pub fn on_ended_request_again(trigger: Trigger<OnActionEnded>, mut commands: Commands) {
+    /// [`Observer`] that listens for [`OnActionEnded`] events of the [`CurrentAction`]
+    /// and triggers a new [`RequestAction`] event for the target actor entity.
+    ///
+    /// Timed out actions are cleared from the [`CurrentAction`] first, so that they are initiated again
+    /// if they are still picked. Failed actions follow their [`FailurePolicy`] from [`ActionFailurePolicies`].
+    ///
+    /// Actors in the [`TurnQueue`] only act during their turns, so their new action is requested on their next turn.
+    ///
+    /// Other actions ending, such as the steps of a [`Sequence`], are ignored.
+    pub fn on_ended_request_again(
+        trigger: Trigger<OnActionEnded>,
+        mut commands: Commands,
+        current_actions: Query<&CurrentAction>,
+        retrying: Query<(), With<ActionRetries>>,
+        queue: Option<Res<TurnQueue>>,
+    ) {
         let actor = trigger.target();
+        if current_actions
+            .get(actor)
+            .is_ok_and(|current_action| current_action.0 != trigger.event().action)
+        {
+            return;
+        }
+        let turn_based = queue.is_some_and(|queue| queue.contains(actor));
+
+        let reason = &trigger.event().reason;
+        if !matches!(reason, ActionEndReason::Failed(_)) && retrying.contains(actor) {
+            // Retries only count failures in a row
+            commands.entity(actor).remove::<ActionRetries>();
+        }
 
-        match trigger.event().reason {
+        match reason {
+            ActionEndReason::TimedOut => {
+                // Pick a new action, restarting the timed out one if it's picked again
+                commands.entity(actor).remove::<(CurrentAction, CurrentTarget)>();
+                if !turn_based {
+                    commands.trigger_targets(RequestAction::pick(), actor);
+                }
+            }
+            ActionEndReason::Failed(_) => {
+                let action = trigger.event().action;
+                commands.queue(move |world: &mut World| ActionFailurePolicies::follow(world, actor, action));
+            }
             ActionEndReason::Completed => {
-                // Pick a new action
-                commands.trigger_targets(RequestAction { action: None }, actor);
+                if !turn_based {
+                    // Pick a new action
+                    commands.trigger_targets(RequestAction::pick(), actor);
+                }
             }
             ActionEndReason::Cancelled => {
                 // Do nothing
@@ -103,6 +284,15 @@ impl ActionPlugin {
 #[reflect(Component)]
 pub struct CurrentAction(pub ComponentId);
 
+/// [`Component`] for the target entity of the [`CurrentAction`], if it was requested or picked with one.
+///
+/// This component is inserted and removed by the [`ActionPlugin`] along with [`CurrentAction`].
+/// See [`Targets`](crate::scoring::Targets) for picking actions with a target.
+#[derive(Component, Reflect)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[reflect(Component)]
+pub struct CurrentTarget(pub Entity);
+
 /// [`Observer`] that listens for [`OnActionInitiated`] events targeting
 /// the specified `Action` [`Component`] and inserts a [`Default`] instance of it
 /// onto the actor entity.
@@ -136,3 +326,787 @@ pub fn on_action_ended_remove<Action: Component>(trigger: Trigger<OnActionEnded,
     let actor = trigger.target();
     commands.entity(actor).remove::<Action>();
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::{ecs::component::ComponentId, prelude::*};
+
+    use crate::{
+        TurnMode, TurnQueue,
+        acting::{
+            ActionDeadlines, ActionFailurePolicies, ActionInterrupts, ActionLayer, ActionPlugin, ActionRetries,
+            ActionSequences, ActionTimeouts, CurrentAction, CurrentTarget, FailurePenalty, FailurePolicy,
+            Interruptibility, ProtectedActions, Sequence, SequenceProgress, on_action_ended_remove,
+            on_action_initiated_insert_default,
+        },
+        ecs::TargetedAction,
+        event::{
+            AdvanceTurn, OnActionEnded, OnActionInitiated, OnActionRejected, OnPicked, RequestAction, RunPicking,
+            RunScoring,
+        },
+        picking::{Highest, Picker, PickingPlugin},
+        scoring::{CooldownLength, FixedScore, Score, ScoreOf, ScoringPlugin, SkipScoringEvents, Targets},
+    };
+
+    #[derive(Component)]
+    struct Patrol;
+
+    #[derive(Component, Default)]
+    struct Walk;
+
+    #[derive(Component, Default)]
+    struct LookAround;
+
+    #[derive(Component)]
+    struct Idle;
+
+    #[test]
+    fn sequence_completed() {
+        let mut app = App::new();
+        app.add_plugins(crate::ObservedUtilityPlugins::TurnBased);
+        let world = app.world_mut();
+
+        let patrol = world.register_component::<Patrol>();
+        let walk = world.register_component::<Walk>();
+        let look_around = world.register_component::<LookAround>();
+        let idle = world.register_component::<Idle>();
+
+        world
+            .resource_mut::<ActionSequences>()
+            .insert(patrol, Sequence::new([walk, look_around]));
+        world.add_observer(on_action_initiated_insert_default::<Walk>);
+        world.add_observer(on_action_initiated_insert_default::<LookAround>);
+        world.add_observer(on_action_ended_remove::<Walk>);
+        world.add_observer(on_action_ended_remove::<LookAround>);
+
+        let actor = world.spawn(Picker::new(idle)).id();
+        world.trigger_targets(RequestAction::action(patrol), actor);
+        world.flush();
+
+        assert_eq!(Some(&CurrentAction(patrol)), world.get::<CurrentAction>(actor));
+        assert!(world.entity(actor).contains::<Walk>());
+
+        world.trigger_targets(OnActionEnded::completed(walk), TargetedAction(actor, walk));
+        world.flush();
+
+        // The step completing doesn't re-request an action
+        assert_eq!(Some(&CurrentAction(patrol)), world.get::<CurrentAction>(actor));
+        assert!(!world.entity(actor).contains::<Walk>());
+        assert!(world.entity(actor).contains::<LookAround>());
+
+        world.trigger_targets(
+            OnActionEnded::completed(look_around),
+            TargetedAction(actor, look_around),
+        );
+        world.flush();
+
+        // The sequence completing re-requests the picked action
+        assert_eq!(Some(&CurrentAction(idle)), world.get::<CurrentAction>(actor));
+        assert!(!world.entity(actor).contains::<LookAround>());
+        assert!(!world.entity(actor).contains::<SequenceProgress>());
+    }
+
+    #[test]
+    #[should_panic = "must not be sequences themselves"]
+    fn sequence_of_sequences() {
+        let mut world = World::new();
+        let patrol = world.register_component::<Patrol>();
+        let walk = world.register_component::<Walk>();
+        let look_around = world.register_component::<LookAround>();
+
+        let mut sequences = ActionSequences::default();
+        sequences.insert(walk, Sequence::new([look_around]));
+        sequences.insert(patrol, Sequence::new([walk, look_around]));
+    }
+
+    #[test]
+    #[should_panic = "must not be sequences themselves"]
+    fn sequence_of_itself() {
+        let mut world = World::new();
+        let patrol = world.register_component::<Patrol>();
+        let walk = world.register_component::<Walk>();
+
+        let mut sequences = ActionSequences::default();
+        sequences.insert(patrol, Sequence::new([walk, patrol]));
+    }
+
+    #[test]
+    #[should_panic = "must not be a step of another sequence"]
+    fn sequence_as_step() {
+        let mut world = World::new();
+        let patrol = world.register_component::<Patrol>();
+        let walk = world.register_component::<Walk>();
+        let look_around = world.register_component::<LookAround>();
+
+        let mut sequences = ActionSequences::default();
+        sequences.insert(patrol, Sequence::new([walk, look_around]));
+        sequences.insert(walk, Sequence::new([look_around]));
+    }
+
+    #[test]
+    fn sequence_cancelled() {
+        #[derive(Resource, Default)]
+        struct Ended(Vec<(Entity, OnActionEnded)>);
+
+        let mut app = App::new();
+        app.add_plugins(crate::ObservedUtilityPlugins::TurnBased);
+        let world = app.world_mut();
+
+        let patrol = world.register_component::<Patrol>();
+        let walk = world.register_component::<Walk>();
+        let look_around = world.register_component::<LookAround>();
+        let idle = world.register_component::<Idle>();
+
+        world
+            .resource_mut::<ActionSequences>()
+            .insert(patrol, Sequence::new([walk, look_around]));
+        world.init_resource::<Ended>();
+        world.add_observer(|trigger: Trigger<OnActionEnded>, mut ended: ResMut<Ended>| {
+            ended.0.push((trigger.target(), trigger.event().clone()));
+        });
+
+        // Cancelling a step cancels the sequence
+        let actor = world.spawn(Picker::new(idle)).id();
+        world.trigger_targets(RequestAction::action(patrol), actor);
+        world.flush();
+        world.trigger_targets(OnActionEnded::cancelled(walk), TargetedAction(actor, walk));
+        world.flush();
+
+        assert_eq!(
+            vec![
+                (actor, OnActionEnded::cancelled(walk)),
+                (actor, OnActionEnded::cancelled(patrol))
+            ],
+            std::mem::take(&mut world.resource_mut::<Ended>().0)
+        );
+        assert!(!world.entity(actor).contains::<SequenceProgress>());
+
+        // Cancelling the sequence cancels the current step
+        let actor = world.spawn(Picker::new(idle)).id();
+        world.trigger_targets(RequestAction::action(patrol), actor);
+        world.flush();
+        world.trigger_targets(RequestAction::action(idle), actor);
+        world.flush();
+
+        assert_eq!(
+            vec![
+                (actor, OnActionEnded::cancelled(patrol)),
+                (actor, OnActionEnded::cancelled(walk))
+            ],
+            world.resource::<Ended>().0
+        );
+        assert!(!world.entity(actor).contains::<SequenceProgress>());
+        assert_eq!(Some(&CurrentAction(idle)), world.get::<CurrentAction>(actor));
+    }
+
+    #[test]
+    fn sequence_timed_out() {
+        #[derive(Resource, Default)]
+        struct Ended(Vec<(Entity, OnActionEnded)>);
+
+        let mut app = App::new();
+        app.add_plugins(crate::ObservedUtilityPlugins::TurnBased);
+        app.init_resource::<Time>();
+        let world = app.world_mut();
+
+        let patrol = world.register_component::<Patrol>();
+        let walk = world.register_component::<Walk>();
+        let look_around = world.register_component::<LookAround>();
+        let idle = world.register_component::<Idle>();
+
+        world
+            .resource_mut::<ActionSequences>()
+            .insert(patrol, Sequence::new([walk, look_around]));
+        world
+            .resource_mut::<ActionTimeouts>()
+            .insert(walk, Duration::from_secs(2));
+        world.init_resource::<Ended>();
+        world.add_observer(|trigger: Trigger<OnActionEnded>, mut ended: ResMut<Ended>| {
+            ended.0.push((trigger.target(), trigger.event().clone()));
+        });
+
+        // Only the step has a timeout, but timing it out times out the sequence
+        let actor = world.spawn(Picker::new(idle)).id();
+        world.trigger_targets(RequestAction::action(patrol), actor);
+        world.flush();
+        assert_eq!(
+            Some(Duration::from_secs(2)),
+            world
+                .get::<ActionDeadlines>(actor)
+                .and_then(|deadlines| deadlines.get(walk))
+        );
+        assert_eq!(None, world.get::<ActionDeadlines>(actor).unwrap().get(patrol));
+
+        world.resource_mut::<Time>().advance_by(Duration::from_secs(1));
+        app.update();
+        assert!(app.world().resource::<Ended>().0.is_empty());
+
+        app.world_mut()
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_secs(1));
+        app.update();
+        let world = app.world_mut();
+
+        assert_eq!(
+            vec![
+                (actor, OnActionEnded::timed_out(walk)),
+                (actor, OnActionEnded::timed_out(patrol))
+            ],
+            world.resource::<Ended>().0
+        );
+        assert!(!world.entity(actor).contains::<SequenceProgress>());
+        assert_eq!(None, world.get::<ActionDeadlines>(actor).unwrap().get(walk));
+        // Timing out requests the picked action again
+        assert_eq!(Some(&CurrentAction(idle)), world.get::<CurrentAction>(actor));
+    }
+
+    #[test]
+    fn layers() {
+        #[derive(Component)]
+        struct Talk;
+
+        #[derive(Component)]
+        struct Fall;
+
+        #[derive(Resource, Default)]
+        struct Rejected(Vec<(Entity, OnActionRejected)>);
+
+        let mut app = App::new();
+        app.add_plugins(crate::ObservedUtilityPlugins::TurnBased)
+            .init_resource::<Rejected>()
+            .add_observer(|trigger: Trigger<OnActionRejected>, mut rejected: ResMut<Rejected>| {
+                rejected.0.push((trigger.target(), *trigger.event()));
+            });
+        let world = app.world_mut();
+
+        let walk = world.register_component::<Walk>();
+        let talk = world.register_component::<Talk>();
+        let fall = world.register_component::<Fall>();
+        let idle = world.register_component::<Idle>();
+
+        let actor = world.spawn_empty().id();
+        let legs = world
+            .spawn((ActionLayer::default(), Picker::new(idle), ChildOf(actor)))
+            .id();
+        let mouth = world
+            .spawn((ActionLayer::default(), Picker::new(idle), ChildOf(actor)))
+            .id();
+        let body = world
+            .spawn((ActionLayer::exclusive(), Picker::new(idle), ChildOf(actor)))
+            .id();
+
+        // Layers don't cancel each other
+        world.trigger_targets(RequestAction::action(walk), legs);
+        world.trigger_targets(RequestAction::action(talk), mouth);
+        world.flush();
+
+        assert_eq!(Some(&CurrentAction(walk)), world.get::<CurrentAction>(legs));
+        assert_eq!(Some(&CurrentAction(talk)), world.get::<CurrentAction>(mouth));
+
+        // Exclusive layers cancel the other layers
+        world.trigger_targets(RequestAction::action(fall), body);
+        world.flush();
+
+        assert_eq!(Some(&CurrentAction(idle)), world.get::<CurrentAction>(legs));
+        assert_eq!(Some(&CurrentAction(idle)), world.get::<CurrentAction>(mouth));
+        assert_eq!(Some(&CurrentAction(fall)), world.get::<CurrentAction>(body));
+
+        // And block them until they're done
+        world.trigger_targets(RequestAction::action(walk), legs);
+        world.flush();
+
+        assert_eq!(Some(&CurrentAction(idle)), world.get::<CurrentAction>(legs));
+        assert_eq!(
+            vec![(
+                legs,
+                OnActionRejected {
+                    action: walk,
+                    target: None,
+                    current: fall
+                }
+            )],
+            world.resource::<Rejected>().0
+        );
+
+        world.trigger_targets(RequestAction::action(idle), body);
+        world.flush();
+        world.trigger_targets(RequestAction::action(walk), legs);
+        world.flush();
+
+        assert_eq!(Some(&CurrentAction(walk)), world.get::<CurrentAction>(legs));
+    }
+
+    #[test]
+    fn picked_target() {
+        #[derive(Component)]
+        struct Attack;
+
+        let mut app = App::new();
+        app.add_plugins(crate::ObservedUtilityPlugins::TurnBased);
+        let world = app.world_mut();
+
+        let attack = world.register_component::<Attack>();
+        let idle = world.register_component::<Idle>();
+
+        let enemy = world.spawn_empty().id();
+        let attacking = world
+            .spawn((Score::default(), FixedScore::new(0.7), Targets::new([enemy])))
+            .id();
+        let actor = world
+            .spawn((Picker::new(idle).with(attacking, attack), Highest))
+            .add_child(attacking)
+            .id();
+
+        #[derive(Resource, Default)]
+        struct Events(Vec<OnPicked>, Vec<OnActionInitiated>);
+        world.init_resource::<Events>();
+        world.add_observer(|trigger: Trigger<OnPicked>, mut events: ResMut<Events>| {
+            events.0.push(*trigger.event());
+        });
+        world.add_observer(|trigger: Trigger<OnActionInitiated>, mut events: ResMut<Events>| {
+            events.1.push(*trigger.event());
+        });
+        world.flush();
+
+        world.trigger(RunScoring);
+        world.flush();
+        world.trigger_targets(RunPicking, actor);
+        world.flush();
+        world.trigger_targets(RequestAction::default(), actor);
+        world.flush();
+
+        let events = world.resource::<Events>();
+        assert_eq!(vec![OnPicked::new(attack).with_target(enemy)], events.0);
+        assert_eq!(vec![OnActionInitiated::new(attack).with_target(enemy)], events.1);
+        assert_eq!(Some(&CurrentTarget(enemy)), world.get::<CurrentTarget>(actor));
+
+        // Switching to an action without a target removes the current target
+        world.trigger_targets(RequestAction::action(idle), actor);
+        world.flush();
+
+        assert_eq!(Some(&CurrentAction(idle)), world.get::<CurrentAction>(actor));
+        assert_eq!(None, world.get::<CurrentTarget>(actor));
+    }
+
+    #[test]
+    fn failed_penalized() {
+        #[derive(Component)]
+        struct Run;
+
+        for parallel in [false, true] {
+            let mut app = App::new();
+            app.add_plugins((
+                ScoringPlugin {
+                    parallel,
+                    ..Default::default()
+                },
+                PickingPlugin,
+                ActionPlugin,
+            ));
+            let world = app.world_mut();
+
+            let walk = world.register_component::<Walk>();
+            let run = world.register_component::<Run>();
+            let idle = world.register_component::<Idle>();
+
+            world.resource_mut::<ActionFailurePolicies>().insert(
+                walk,
+                FailurePolicy::Penalize {
+                    penalty: 0.5,
+                    length: CooldownLength::Turns(1),
+                },
+            );
+
+            let walking = world
+                .spawn((Score::default(), FixedScore::new(0.8), SkipScoringEvents))
+                .id();
+            let running = world
+                .spawn((Score::default(), FixedScore::new(0.6), SkipScoringEvents))
+                .id();
+            let actor = world
+                .spawn((Picker::new(idle).with(walking, walk).with(running, run), Highest))
+                .add_children(&[walking, running])
+                .id();
+            world.flush();
+
+            world.trigger(RunScoring);
+            world.flush();
+            world.trigger_targets(RunPicking, actor);
+            world.flush();
+            world.trigger_targets(RequestAction::default(), actor);
+            world.flush();
+            assert_eq!(Some(&CurrentAction(walk)), world.get::<CurrentAction>(actor));
+
+            // Failing penalizes walking right away, so running is picked instead
+            world.trigger_targets(OnActionEnded::failed(walk, "no path"), TargetedAction(actor, walk));
+            world.flush();
+            assert!(matches!(
+                world.get::<Score>(walking).map(Score::get),
+                Some(score) if (score - 0.3).abs() < 1e-6
+            ));
+            assert_eq!(Some(&CurrentAction(run)), world.get::<CurrentAction>(actor));
+
+            // The penalty is over after one scoring run
+            world.trigger(RunScoring);
+            world.flush();
+            assert_eq!(Some(0.8), world.get::<Score>(walking).map(Score::get));
+            assert!(!world.entity(walking).contains::<FailurePenalty>());
+        }
+    }
+
+    #[test]
+    fn failed_retries_reset() {
+        let mut app = App::new();
+        app.add_plugins((PickingPlugin, ActionPlugin));
+        let world = app.world_mut();
+
+        let walk = world.register_component::<Walk>();
+        let idle = world.register_component::<Idle>();
+
+        world
+            .resource_mut::<ActionFailurePolicies>()
+            .insert(walk, FailurePolicy::Retry(1));
+
+        let target = world.spawn_empty().id();
+        let actor = world.spawn(Picker::new(idle)).id();
+        world.trigger_targets(RequestAction::action(walk), actor);
+        world.flush();
+
+        let reason = format!("no path to {target}");
+        world.trigger_targets(OnActionEnded::failed(walk, reason), TargetedAction(actor, walk));
+        world.flush();
+        assert_eq!(Some(1), world.get::<ActionRetries>(actor).map(ActionRetries::retries));
+
+        // Cancelling the retried action resets its retries
+        world.trigger_targets(RequestAction::action(idle), actor);
+        world.flush();
+        assert_eq!(None, world.get::<ActionRetries>(actor));
+    }
+
+    #[test]
+    fn failed_penalized_unchanged() {
+        let mut app = App::new();
+        app.add_plugins((ScoringPlugin::default(), PickingPlugin, ActionPlugin));
+        let world = app.world_mut();
+        world.init_resource::<Time>();
+
+        let walk = world.register_component::<Walk>();
+        let idle = world.register_component::<Idle>();
+
+        world.resource_mut::<ActionFailurePolicies>().insert(
+            walk,
+            FailurePolicy::Penalize {
+                penalty: 0.5,
+                length: CooldownLength::Time(Duration::from_secs(1)),
+            },
+        );
+
+        let walking = world.spawn((Score::default(), FixedScore::new(0.8))).id();
+        let actor = world
+            .spawn((Picker::new(idle).with(walking, walk), Highest))
+            .add_child(walking)
+            .id();
+        world.flush();
+
+        world.trigger_targets(RequestAction::action(walk), actor);
+        world.flush();
+        world.trigger_targets(OnActionEnded::failed(walk, "no path"), TargetedAction(actor, walk));
+        world.flush();
+
+        // A time-based penalty isn't marked as changed by scoring
+        let last_changed = world
+            .entity(walking)
+            .get_change_ticks::<FailurePenalty>()
+            .unwrap()
+            .changed;
+        world.increment_change_tick();
+        world.trigger(RunScoring);
+        world.flush();
+        assert!(matches!(
+            world.get::<Score>(walking).map(Score::get),
+            Some(score) if (score - 0.3).abs() < 1e-6
+        ));
+        assert_eq!(
+            last_changed,
+            world
+                .entity(walking)
+                .get_change_ticks::<FailurePenalty>()
+                .unwrap()
+                .changed
+        );
+    }
+
+    #[test]
+    fn uninterruptible() {
+        #[derive(Resource, Default)]
+        struct Rejected(Vec<(Entity, OnActionRejected)>);
+
+        let mut app = App::new();
+        app.add_plugins(crate::ObservedUtilityPlugins::TurnBased);
+        let world = app.world_mut();
+
+        let walk = world.register_component::<Walk>();
+        let look_around = world.register_component::<LookAround>();
+        let idle = world.register_component::<Idle>();
+
+        world
+            .resource_mut::<ActionInterrupts>()
+            .insert(walk, Interruptibility::Uninterruptible);
+        world.init_resource::<Rejected>();
+        world.add_observer(|trigger: Trigger<OnActionRejected>, mut rejected: ResMut<Rejected>| {
+            rejected.0.push((trigger.target(), *trigger.event()));
+        });
+
+        let actor = world.spawn(Picker::new(idle)).id();
+        world.trigger_targets(RequestAction::action(walk), actor);
+        world.flush();
+        assert!(world.get::<ProtectedActions>(actor).unwrap().contains(walk));
+
+        // Not even the default action interrupts it
+        world.trigger_targets(RequestAction::action(idle), actor);
+        world.flush();
+        assert_eq!(Some(&CurrentAction(walk)), world.get::<CurrentAction>(actor));
+        assert_eq!(
+            vec![(
+                actor,
+                OnActionRejected {
+                    action: idle,
+                    target: None,
+                    current: walk
+                }
+            )],
+            world.resource::<Rejected>().0
+        );
+
+        // Once it's done, any action can be requested
+        world.trigger_targets(OnActionEnded::completed(walk), TargetedAction(actor, walk));
+        world.flush();
+        assert!(!world.get::<ProtectedActions>(actor).unwrap().contains(walk));
+        world.trigger_targets(RequestAction::action(look_around), actor);
+        world.flush();
+        assert_eq!(Some(&CurrentAction(look_around)), world.get::<CurrentAction>(actor));
+        assert_eq!(1, world.resource::<Rejected>().0.len());
+    }
+
+    #[test]
+    fn turns_simultaneous() {
+        #[derive(Resource, Default)]
+        struct Initiated(Vec<Entity>);
+
+        let mut app = App::new();
+        app.add_plugins(crate::ObservedUtilityPlugins::TurnBased)
+            .init_resource::<Initiated>()
+            .add_observer(
+                |trigger: Trigger<OnActionInitiated>, mut initiated: ResMut<Initiated>| {
+                    initiated.0.push(trigger.target());
+                },
+            );
+        let world = app.world_mut();
+        world.resource_mut::<TurnQueue>().mode = TurnMode::Simultaneous;
+
+        let walk = world.register_component::<Walk>();
+        let idle = world.register_component::<Idle>();
+
+        let actors: Vec<Entity> = (0..2)
+            .map(|_| {
+                let actor = world.spawn(Highest).id();
+                let score = world
+                    .spawn((FixedScore::new(0.5), Score::default(), ScoreOf(actor)))
+                    .id();
+                world.entity_mut(actor).insert(Picker::new(idle).with(score, walk));
+                world.resource_mut::<TurnQueue>().push(actor);
+                actor
+            })
+            .collect();
+
+        world.trigger(AdvanceTurn);
+        world.flush();
+        assert_eq!(&actors[..], world.resource::<TurnQueue>().acting());
+        assert_eq!(actors, world.resource::<Initiated>().0);
+
+        // Waiting for both actors
+        world.trigger_targets(OnActionEnded::completed(walk), TargetedAction(actors[0], walk));
+        world.flush();
+        world.trigger(AdvanceTurn);
+        world.flush();
+        assert_eq!(&actors[1..], world.resource::<TurnQueue>().acting());
+        assert_eq!(1, world.resource::<TurnQueue>().turn());
+
+        // The same action is initiated again on the next turn
+        world.trigger_targets(OnActionEnded::completed(walk), TargetedAction(actors[1], walk));
+        world.flush();
+        world.trigger(AdvanceTurn);
+        world.flush();
+        assert_eq!(&actors[..], world.resource::<TurnQueue>().acting());
+        assert_eq!(4, world.resource::<Initiated>().0.len());
+
+        // Despawned actors leave the queue
+        world.trigger_targets(OnActionEnded::completed(walk), TargetedAction(actors[0], walk));
+        world.entity_mut(actors[1]).despawn();
+        world.flush();
+        world.trigger(AdvanceTurn);
+        world.flush();
+        assert_eq!(&actors[..1], world.resource::<TurnQueue>().actors());
+        assert_eq!(&actors[..1], world.resource::<TurnQueue>().acting());
+    }
+
+    #[test]
+    fn turns_despawned() {
+        let mut app = App::new();
+        app.add_plugins(crate::ObservedUtilityPlugins::TurnBased);
+        let world = app.world_mut();
+        world.resource_mut::<TurnQueue>().mode = TurnMode::Simultaneous;
+
+        let walk = world.register_component::<Walk>();
+        let idle = world.register_component::<Idle>();
+
+        let actors: Vec<Entity> = (0..2)
+            .map(|_| {
+                let actor = world.spawn(Highest).id();
+                let score = world
+                    .spawn((FixedScore::new(0.5), Score::default(), ScoreOf(actor)))
+                    .id();
+                world.entity_mut(actor).insert(Picker::new(idle).with(score, walk));
+                world.resource_mut::<TurnQueue>().push(actor);
+                actor
+            })
+            .collect();
+
+        // The first actor's action despawns the second actor before it acts
+        let victim = actors[1];
+        world.add_observer(move |trigger: Trigger<OnActionInitiated>, mut commands: Commands| {
+            if trigger.target() != victim {
+                commands.entity(victim).despawn();
+            }
+        });
+
+        world.trigger(AdvanceTurn);
+        world.flush();
+        assert_eq!(&actors[..1], world.resource::<TurnQueue>().actors());
+        assert_eq!(&actors[..1], world.resource::<TurnQueue>().acting());
+    }
+
+    #[test]
+    fn turns_not_initiated() {
+        let mut app = App::new();
+        app.add_plugins(crate::ObservedUtilityPlugins::TurnBased);
+        let world = app.world_mut();
+
+        let walk = world.register_component::<Walk>();
+
+        // Without a picker, requesting an action does nothing
+        let statue = world.spawn_empty().id();
+        let actor = world.spawn(Picker::new(walk)).id();
+        world.resource_mut::<TurnQueue>().push(statue);
+        world.resource_mut::<TurnQueue>().push(actor);
+
+        world.trigger(AdvanceTurn);
+        world.flush();
+        assert!(world.resource::<TurnQueue>().acting().is_empty());
+        assert_eq!(1, world.resource::<TurnQueue>().turn());
+
+        // The next turn isn't blocked by the statue
+        world.trigger(AdvanceTurn);
+        world.flush();
+        assert_eq!(&[actor], world.resource::<TurnQueue>().acting());
+        assert_eq!(Some(&CurrentAction(walk)), world.get::<CurrentAction>(actor));
+
+        // Actions ending right away end the turn too
+        world.add_observer(move |trigger: Trigger<OnActionInitiated>, mut commands: Commands| {
+            let action = trigger.event().action;
+            commands.trigger_targets(
+                OnActionEnded::completed(action),
+                TargetedAction(trigger.target(), action),
+            );
+        });
+        world.trigger_targets(OnActionEnded::completed(walk), TargetedAction(actor, walk));
+        world.flush();
+        world.trigger(AdvanceTurn);
+        world.flush();
+        world.trigger(AdvanceTurn);
+        world.flush();
+        assert!(world.resource::<TurnQueue>().acting().is_empty());
+        assert_eq!(4, world.resource::<TurnQueue>().turn());
+    }
+
+    #[test]
+    fn turns_timed_out_and_failed() {
+        #[derive(Resource, Default)]
+        struct Initiated(Vec<ComponentId>);
+
+        let mut app = App::new();
+        app.add_plugins(crate::ObservedUtilityPlugins::TurnBased)
+            .init_resource::<Time>()
+            .init_resource::<Initiated>()
+            .add_observer(
+                |trigger: Trigger<OnActionInitiated>, mut initiated: ResMut<Initiated>| {
+                    initiated.0.push(trigger.event().action);
+                },
+            );
+        let world = app.world_mut();
+
+        let walk = world.register_component::<Walk>();
+        let idle = world.register_component::<Idle>();
+        world
+            .resource_mut::<ActionTimeouts>()
+            .insert(walk, Duration::from_secs(5));
+        world
+            .resource_mut::<ActionFailurePolicies>()
+            .insert(walk, FailurePolicy::Retry(1));
+
+        let actor = world.spawn(Highest).id();
+        let score = world
+            .spawn((FixedScore::new(0.5), Score::default(), ScoreOf(actor)))
+            .id();
+        let rest = world
+            .spawn((FixedScore::new(0.0), Score::default(), ScoreOf(actor)))
+            .id();
+        world
+            .entity_mut(actor)
+            .insert(Picker::new(idle).with(score, walk).with(rest, idle));
+        world.resource_mut::<TurnQueue>().push(actor);
+
+        world.trigger(AdvanceTurn);
+        world.flush();
+        assert_eq!(vec![walk], world.resource::<Initiated>().0);
+
+        // Timing out ends the turn without requesting another action
+        world.resource_mut::<Time>().advance_by(Duration::from_secs(5));
+        app.update();
+        let world = app.world_mut();
+        assert!(world.resource::<TurnQueue>().acting().is_empty());
+        assert_eq!(None, world.get::<CurrentAction>(actor));
+        assert_eq!(None, world.get::<ActionDeadlines>(actor).unwrap().get(walk));
+        assert_eq!(1, world.resource::<Initiated>().0.len());
+
+        world.trigger(AdvanceTurn);
+        world.flush();
+        assert_eq!(&[actor], world.resource::<TurnQueue>().acting());
+        assert_eq!(vec![walk, walk], world.resource::<Initiated>().0);
+
+        // Failing too, and the retry is deferred to the next turn even if something else is picked
+        world.trigger_targets(OnActionEnded::failed(walk, "blocked"), TargetedAction(actor, walk));
+        world.flush();
+        assert!(world.resource::<TurnQueue>().acting().is_empty());
+        assert_eq!(None, world.get::<CurrentAction>(actor));
+        assert_eq!(2, world.resource::<Initiated>().0.len());
+
+        world.get_mut::<FixedScore>(rest).unwrap().set_value(1.0);
+        world.trigger(AdvanceTurn);
+        world.flush();
+        assert_eq!(&[actor], world.resource::<TurnQueue>().acting());
+        assert_eq!(Some(&CurrentAction(walk)), world.get::<CurrentAction>(actor));
+        assert_eq!(1, world.get::<ActionRetries>(actor).unwrap().retries());
+        assert_eq!(3, world.resource::<Initiated>().0.len());
+
+        // Out of retries, the picked action is initiated on the next turn
+        world.trigger_targets(OnActionEnded::failed(walk, "blocked"), TargetedAction(actor, walk));
+        world.flush();
+        world.trigger(AdvanceTurn);
+        world.flush();
+        assert_eq!(Some(&CurrentAction(idle)), world.get::<CurrentAction>(actor));
+        assert_eq!(vec![walk, walk, walk, idle], world.resource::<Initiated>().0);
+    }
+}
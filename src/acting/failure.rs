@@ -0,0 +1,274 @@
+use std::time::Duration;
+
+use bevy::{
+    ecs::component::{ComponentHooks, ComponentId, Mutable, StorageType},
+    platform::collections::HashMap,
+    prelude::*,
+};
+
+use crate::{
+    TurnQueue,
+    acting::{CurrentAction, CurrentTarget},
+    ecs::CommandsExt,
+    event::{OnScorePostProcess, RequestAction},
+    picking::Picker,
+    score_and_pick_actor,
+    scoring::{ComparingTargets, Cooldown, CooldownLength, Score},
+};
+
+/// What happens after the [`CurrentAction`] of an actor entity ends with
+/// [`ActionEndReason::Failed`](crate::event::ActionEndReason::Failed).
+///
+/// In every case, the failed action is cleared from the [`CurrentAction`] first,
+/// so that it's initiated again if it's still picked.
+#[derive(Reflect)]
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+#[reflect(PartialEq, Debug, Default)]
+pub enum FailurePolicy {
+    /// Request a new action right away, like when the action completes.
+    #[default]
+    Repick,
+    /// Initiate the failed action again, with the same target, up to the given number of times in a row.
+    /// Once all retries have failed too, request a new action.
+    Retry(u32),
+    /// Lower the scores of the [`Picker`] choices mapped to the failed action by `penalty` for a while,
+    /// then score, pick, and request a new action for the actor entity.
+    ///
+    /// The length is measured like a [`Cooldown`], and the penalty is restarted by every failure.
+    Penalize {
+        /// The amount subtracted from the penalized scores.
+        penalty: f32,
+        /// How long the penalty lasts.
+        length: CooldownLength,
+    },
+}
+
+/// [`Resource`] holding the [`FailurePolicy`] of actions, keyed by action [`ComponentId`].
+///
+/// Actions without a registered policy use [`FailurePolicy::Repick`].
+/// The policy is followed by [`ActionPlugin::on_ended_request_again`](crate::acting::ActionPlugin::on_ended_request_again).
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_observed_utility::{event::RequestAction, prelude::*};
+///
+/// # let mut app = App::new();
+/// # app.add_plugins(ObservedUtilityPlugins::TurnBased);
+/// # let mut world = app.world_mut();
+/// #[derive(Component)]
+/// pub struct Walk;
+/// #[derive(Component)]
+/// pub struct Idle;
+///
+/// let walk = world.register_component::<Walk>();
+/// let idle = world.register_component::<Idle>();
+///
+/// world
+///     .resource_mut::<ActionFailurePolicies>()
+///     .insert(walk, FailurePolicy::Retry(1));
+///
+/// let actor = world.spawn(Picker::new(idle)).id();
+/// world.trigger_targets(RequestAction::action(walk), actor);
+/// # world.flush();
+///
+/// // The first failure is retried...
+/// world.trigger_targets(OnActionEnded::failed(walk, "no path"), TargetedAction(actor, walk));
+/// # world.flush();
+/// assert_eq!(Some(&CurrentAction(walk)), world.get::<CurrentAction>(actor));
+///
+/// // ...but the second one isn't.
+/// world.trigger_targets(OnActionEnded::failed(walk, "no path"), TargetedAction(actor, walk));
+/// # world.flush();
+/// assert_eq!(Some(&CurrentAction(idle)), world.get::<CurrentAction>(actor));
+/// ```
+#[derive(Resource, Reflect)]
+#[derive(Clone, PartialEq, Debug, Default)]
+#[reflect(Resource, PartialEq, Debug, Default)]
+pub struct ActionFailurePolicies {
+    policies: HashMap<ComponentId, FailurePolicy>,
+}
+
+impl ActionFailurePolicies {
+    /// Sets the [`FailurePolicy`] of the given action [`ComponentId`], replacing any previous one.
+    pub fn insert(&mut self, action: ComponentId, policy: FailurePolicy) -> Option<FailurePolicy> {
+        self.policies.insert(action, policy)
+    }
+
+    /// Removes the [`FailurePolicy`] of the given action [`ComponentId`], making it [`FailurePolicy::Repick`].
+    pub fn remove(&mut self, action: ComponentId) -> Option<FailurePolicy> {
+        self.policies.remove(&action)
+    }
+
+    /// Returns the [`FailurePolicy`] of the given action [`ComponentId`].
+    #[must_use]
+    pub fn get(&self, action: ComponentId) -> FailurePolicy {
+        self.policies.get(&action).copied().unwrap_or_default()
+    }
+
+    /// Follows the [`FailurePolicy`] of the given action, which just failed as the [`CurrentAction`] of the actor.
+    ///
+    /// For actors in the [`TurnQueue`], the new action is requested on their next turn instead.
+    pub fn follow(world: &mut World, actor: Entity, action: ComponentId) {
+        let policy = world
+            .get_resource::<Self>()
+            .map(|this| this.get(action))
+            .unwrap_or_default();
+        let now = world.get_resource::<Time>().map(|time| time.elapsed());
+        let turn_based = world
+            .get_resource::<TurnQueue>()
+            .is_some_and(|queue| queue.contains(actor));
+        let Ok(mut actor_mut) = world.get_entity_mut(actor) else {
+            return;
+        };
+        let target = actor_mut.get::<CurrentTarget>().map(|target| target.0);
+        actor_mut.remove::<(CurrentAction, CurrentTarget)>();
+
+        let mut request = RequestAction::pick();
+        match policy {
+            FailurePolicy::Repick => {}
+            FailurePolicy::Retry(retries) => {
+                let retried = actor_mut
+                    .get::<ActionRetries>()
+                    .filter(|previous| previous.action == action)
+                    .map_or(0, ActionRetries::retries);
+                if retried < retries {
+                    actor_mut.insert(ActionRetries {
+                        action,
+                        retries: retried + 1,
+                    });
+                    request = RequestAction::action(action).with_target(target);
+                } else {
+                    actor_mut.remove::<ActionRetries>();
+                }
+            }
+            FailurePolicy::Penalize { penalty, length } => {
+                let choices: Vec<Entity> = actor_mut
+                    .get::<Picker>()
+                    .map(|picker| {
+                        picker
+                            .choices
+                            .iter()
+                            .filter(|(_, choice)| **choice == action)
+                            .map(|(&score_entity, _)| score_entity)
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                for score_entity in choices {
+                    if let Ok(mut score_entity) = world.get_entity_mut(score_entity) {
+                        score_entity.insert(FailurePenalty::new(penalty, length, now));
+                    }
+                }
+                if !turn_based {
+                    score_and_pick_actor(world, actor);
+                }
+            }
+        }
+
+        if turn_based {
+            world.resource_mut::<TurnQueue>().defer(actor, request);
+            return;
+        }
+        world.trigger_targets(request, actor);
+        world.flush();
+    }
+}
+
+/// [`Component`] counting how many times in a row an actor entity retried its failed action,
+/// following [`FailurePolicy::Retry`].
+///
+/// This is removed once the action runs out of retries, or an action ends in any other way than failing.
+#[derive(Component, Reflect)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[reflect(Component, PartialEq, Debug)]
+pub struct ActionRetries {
+    action: ComponentId,
+    retries: u32,
+}
+
+impl ActionRetries {
+    /// Returns the action [`ComponentId`] being retried.
+    #[must_use]
+    pub fn action(&self) -> ComponentId {
+        self.action
+    }
+
+    /// Returns how many times the action was retried so far.
+    #[must_use]
+    pub fn retries(&self) -> u32 {
+        self.retries
+    }
+}
+
+/// [`Component`] for [`Score`] entities that lowers their score for a while after their mapped action failed,
+/// following [`FailurePolicy::Penalize`].
+///
+/// This is inserted by [`ActionFailurePolicies::follow`], and removed once the penalty is over.
+#[derive(Reflect)]
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[reflect(Component, PartialEq, Debug)]
+pub struct FailurePenalty {
+    /// The amount subtracted from the score.
+    pub penalty: f32,
+    /// Measures how long the penalty lasts.
+    cooldown: Cooldown,
+}
+
+impl FailurePenalty {
+    /// Creates a new [`FailurePenalty`] started at the given elapsed time.
+    #[must_use]
+    pub fn new(penalty: f32, length: CooldownLength, now: Option<Duration>) -> Self {
+        let mut cooldown = Cooldown::new(length);
+        cooldown.start(now);
+        Self { penalty, cooldown }
+    }
+
+    /// Returns `true` if the penalty is active at the given elapsed time.
+    #[must_use]
+    pub fn is_active(&self, now: Option<Duration>) -> bool {
+        self.cooldown.is_active(now)
+    }
+
+    /// [`Observer`] that lowers the [`Score`] while the [`FailurePenalty`] is active, and removes it afterwards.
+    fn on_post_process(
+        trigger: Trigger<OnScorePostProcess>,
+        mut commands: Commands,
+        mut targets: Query<(&mut Score, &mut FailurePenalty)>,
+        time: Option<Res<Time>>,
+        comparing: Option<Res<ComparingTargets>>,
+    ) {
+        let Ok((mut score, mut penalty)) = targets.get_mut(trigger.target()) else {
+            // The entity isn't penalized.
+            return;
+        };
+
+        if penalty.is_active(time.map(|time| time.elapsed())) {
+            let penalized = score.get() - penalty.penalty;
+            score.set(penalized);
+            if penalty.cooldown.turns_left() > 0 && comparing.is_none() {
+                // Only mutate while counting down, to avoid triggering change detection
+                penalty.cooldown.tick();
+            }
+        } else {
+            commands.entity(trigger.target()).remove::<FailurePenalty>();
+        }
+    }
+}
+
+impl Component for FailurePenalty {
+    type Mutability = Mutable;
+    const STORAGE_TYPE: StorageType = StorageType::Table;
+
+    fn register_component_hooks(hooks: &mut ComponentHooks) {
+        hooks.on_add(|mut world, _ctx| {
+            #[derive(Resource, Default)]
+            struct FailurePenaltyObserverSpawned;
+
+            world
+                .commands()
+                .once::<FailurePenaltyObserverSpawned>()
+                .observe(Self::on_post_process);
+        });
+    }
+}
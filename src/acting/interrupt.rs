@@ -0,0 +1,198 @@
+use bevy::{ecs::component::ComponentId, platform::collections::HashMap, prelude::*};
+
+use crate::event::{OnActionEnded, OnActionInitiated};
+
+/// How the running action of an actor entity can be interrupted by [requests][`RequestAction`] for other actions.
+///
+/// [`RequestAction`]: crate::event::RequestAction
+#[derive(Reflect)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[reflect(PartialEq, Debug)]
+pub enum Interruptibility {
+    /// Interrupted by actions with at least the same priority.
+    ///
+    /// Actions without a registered [`Interruptibility`] have a priority of `0`,
+    /// so they are interrupted by any action and only interrupt actions with a priority of `0`.
+    Priority(u32),
+    /// Never interrupted, so the action keeps running until it completes, fails, or times out.
+    ///
+    /// When requested itself, the action interrupts any other action
+    /// that isn't [`Uninterruptible`](Self::Uninterruptible) too.
+    Uninterruptible,
+}
+
+impl Default for Interruptibility {
+    fn default() -> Self {
+        Self::Priority(0)
+    }
+}
+
+impl Interruptibility {
+    /// Returns the priority of the action when interrupting other actions.
+    #[must_use]
+    pub fn priority(self) -> u32 {
+        match self {
+            Self::Priority(priority) => priority,
+            Self::Uninterruptible => u32::MAX,
+        }
+    }
+
+    /// Returns `true` if an action with this [`Interruptibility`] can be interrupted by an action with the other one.
+    #[must_use]
+    pub fn interrupted_by(self, other: Interruptibility) -> bool {
+        match self {
+            Self::Priority(priority) => other.priority() >= priority,
+            Self::Uninterruptible => false,
+        }
+    }
+}
+
+/// [`Resource`] holding the [`Interruptibility`] of actions, keyed by action [`ComponentId`].
+///
+/// When a different action is [requested][`RequestAction`] while the [`CurrentAction`] is running,
+/// the [`ActionPlugin`] only cancels the current action if it can be interrupted by the requested one.
+/// Otherwise, [`OnActionRejected`] is triggered for the requested action and the current action keeps running.
+/// Once the current action has ended, any action can be requested again.
+///
+/// This also applies to the default action of the [`Picker`],
+/// but not to the default actions that [`exclusive`](ActionLayer::exclusive) layers fall back to.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_observed_utility::{event::{OnActionRejected, RequestAction}, prelude::*};
+///
+/// # let mut app = App::new();
+/// # app.add_plugins(ObservedUtilityPlugins::TurnBased);
+/// # let mut world = app.world_mut();
+/// #[derive(Component)]
+/// pub struct Reload;
+/// #[derive(Component)]
+/// pub struct Shoot;
+/// #[derive(Component)]
+/// pub struct Flee;
+/// #[derive(Component)]
+/// pub struct Idle;
+///
+/// let reload = world.register_component::<Reload>();
+/// let shoot = world.register_component::<Shoot>();
+/// let flee = world.register_component::<Flee>();
+/// let idle = world.register_component::<Idle>();
+///
+/// let mut interrupts = world.resource_mut::<ActionInterrupts>();
+/// interrupts.insert(reload, Interruptibility::Priority(1));
+/// interrupts.insert(flee, Interruptibility::Priority(2));
+///
+/// #[derive(Resource, Default)]
+/// struct Rejected(Vec<OnActionRejected>);
+///
+/// world.init_resource::<Rejected>();
+/// world.add_observer(|trigger: Trigger<OnActionRejected>, mut rejected: ResMut<Rejected>| {
+///     rejected.0.push(*trigger.event());
+/// });
+///
+/// let actor = world.spawn(Picker::new(idle)).id();
+/// world.trigger_targets(RequestAction::action(reload), actor);
+/// # world.flush();
+///
+/// // Shooting can't interrupt reloading...
+/// world.trigger_targets(RequestAction::action(shoot), actor);
+/// # world.flush();
+/// assert_eq!(Some(&CurrentAction(reload)), world.get::<CurrentAction>(actor));
+/// assert_eq!(
+///     vec![OnActionRejected { action: shoot, target: None, current: reload }],
+///     world.resource::<Rejected>().0
+/// );
+///
+/// // ...but fleeing can.
+/// world.trigger_targets(RequestAction::action(flee), actor);
+/// # world.flush();
+/// assert_eq!(Some(&CurrentAction(flee)), world.get::<CurrentAction>(actor));
+/// assert_eq!(1, world.resource::<Rejected>().0.len());
+/// ```
+///
+/// [`RequestAction`]: crate::event::RequestAction
+/// [`OnActionRejected`]: crate::event::OnActionRejected
+/// [`CurrentAction`]: crate::acting::CurrentAction
+/// [`ActionPlugin`]: crate::acting::ActionPlugin
+/// [`ActionLayer`]: crate::acting::ActionLayer
+/// [`Picker`]: crate::picking::Picker
+#[derive(Resource, Reflect)]
+#[derive(Clone, PartialEq, Debug, Default)]
+#[reflect(Resource, PartialEq, Debug, Default)]
+pub struct ActionInterrupts {
+    interruptibilities: HashMap<ComponentId, Interruptibility>,
+}
+
+impl ActionInterrupts {
+    /// Sets the [`Interruptibility`] of the given action [`ComponentId`], replacing any previous one.
+    pub fn insert(&mut self, action: ComponentId, interruptibility: Interruptibility) -> Option<Interruptibility> {
+        self.interruptibilities.insert(action, interruptibility)
+    }
+
+    /// Removes the [`Interruptibility`] of the given action [`ComponentId`], making it interruptible by any action.
+    pub fn remove(&mut self, action: ComponentId) -> Option<Interruptibility> {
+        self.interruptibilities.remove(&action)
+    }
+
+    /// Returns the [`Interruptibility`] of the given action [`ComponentId`].
+    #[must_use]
+    pub fn get(&self, action: ComponentId) -> Interruptibility {
+        self.interruptibilities.get(&action).copied().unwrap_or_default()
+    }
+
+    /// Returns `true` if the running `current` action can be interrupted by the `next` action.
+    #[must_use]
+    pub fn can_interrupt(&self, current: ComponentId, next: ComponentId) -> bool {
+        self.get(current).interrupted_by(self.get(next))
+    }
+
+    /// [`Observer`] that records an action as running when it is initiated, if it's protected from interruptions.
+    pub fn on_initiated_protect(
+        trigger: Trigger<OnActionInitiated>,
+        mut commands: Commands,
+        interrupts: Res<ActionInterrupts>,
+        mut actors: Query<Option<&mut ProtectedActions>>,
+    ) {
+        let actor = trigger.target();
+        let action = trigger.event().action;
+        if interrupts.get(action) == Interruptibility::default() {
+            return;
+        }
+
+        match actors.get_mut(actor) {
+            Ok(Some(mut protected)) if !protected.contains(action) => protected.0.push(action),
+            Ok(None) => {
+                commands.entity(actor).insert(ProtectedActions(vec![action]));
+            }
+            _ => {}
+        }
+    }
+
+    /// [`Observer`] that stops protecting an action when it ends.
+    pub fn on_ended_unprotect(trigger: Trigger<OnActionEnded>, mut actors: Query<&mut ProtectedActions>) {
+        let Ok(mut protected) = actors.get_mut(trigger.target()) else {
+            return;
+        };
+        if protected.contains(trigger.event().action) {
+            protected.0.retain(|&action| action != trigger.event().action);
+        }
+    }
+}
+
+/// [`Component`] listing the running actions of an actor entity that are protected from interruptions.
+///
+/// This is maintained by [`ActionInterrupts`], see it for more information.
+#[derive(Component, Reflect)]
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+#[reflect(Component, PartialEq, Debug, Default)]
+pub struct ProtectedActions(Vec<ComponentId>);
+
+impl ProtectedActions {
+    /// Returns `true` if the given action is running and protected from interruptions.
+    #[must_use]
+    pub fn contains(&self, action: ComponentId) -> bool {
+        self.0.contains(&action)
+    }
+}
@@ -0,0 +1,94 @@
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+/// [`Component`] that marks an entity as an action layer of its parent actor entity,
+/// allowing the actor to perform multiple actions at the same time, e.g. walking and talking.
+///
+/// Each layer is a child entity of the actor with its own [`Picker`], [`CurrentAction`] and [`Score`] children.
+/// [`RequestAction`] events target the layer entity and only cancel the action of that layer.
+/// Action [`Observer`]s receive the layer entity as the target, whose parent is the actor.
+///
+/// If a layer is [`exclusive`](ActionLayer::exclusive), performing a non-default action on it
+/// cancels the actions of its sibling layers, which then perform their default action until it's done.
+/// Requests for other actions on the sibling layers are rejected with [`OnActionRejected`] in the meantime.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_observed_utility::{event::RequestAction, prelude::*};
+///
+/// # let mut app = App::new();
+/// # app.add_plugins(ObservedUtilityPlugins::TurnBased);
+/// # let mut world = app.world_mut();
+/// #[derive(Component)]
+/// pub struct Walk;
+/// #[derive(Component)]
+/// pub struct Talk;
+/// #[derive(Component)]
+/// pub struct Idle;
+///
+/// let walk = world.register_component::<Walk>();
+/// let talk = world.register_component::<Talk>();
+/// let idle = world.register_component::<Idle>();
+///
+/// let actor = world.spawn_empty().id();
+/// let legs = world.spawn((ActionLayer::default(), Picker::new(idle), ChildOf(actor))).id();
+/// let mouth = world.spawn((ActionLayer::default(), Picker::new(idle), ChildOf(actor))).id();
+///
+/// world.trigger_targets(RequestAction::action(walk), legs);
+/// world.trigger_targets(RequestAction::action(talk), mouth);
+/// world.flush();
+///
+/// // Both actions are performed at the same time.
+/// assert_eq!(Some(&CurrentAction(walk)), world.get::<CurrentAction>(legs));
+/// assert_eq!(Some(&CurrentAction(talk)), world.get::<CurrentAction>(mouth));
+/// ```
+///
+/// [`Picker`]: crate::picking::Picker
+/// [`CurrentAction`]: crate::acting::CurrentAction
+/// [`Score`]: crate::scoring::Score
+/// [`RequestAction`]: crate::event::RequestAction
+/// [`OnActionRejected`]: crate::event::OnActionRejected
+#[derive(Component, Reflect)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[reflect(Component, PartialEq, Debug, Default)]
+pub struct ActionLayer {
+    /// Whether performing a non-default action on this layer cancels and blocks the actions of its sibling layers.
+    pub exclusive: bool,
+}
+
+impl ActionLayer {
+    /// Creates a new [`exclusive`](ActionLayer::exclusive) [`ActionLayer`].
+    #[must_use]
+    pub fn exclusive() -> Self {
+        Self { exclusive: true }
+    }
+}
+
+/// [`SystemParam`] for finding the sibling [`ActionLayer`]s of an action layer entity.
+#[derive(SystemParam)]
+pub struct ActionLayers<'w, 's> {
+    layers: Query<'w, 's, (&'static ActionLayer, &'static ChildOf)>,
+    children: Query<'w, 's, &'static Children>,
+}
+
+impl ActionLayers<'_, '_> {
+    /// Returns the given entity's [`ActionLayer`], if it is one.
+    #[must_use]
+    pub fn get(&self, layer: Entity) -> Option<&ActionLayer> {
+        self.layers.get(layer).ok().map(|(layer, _)| layer)
+    }
+
+    /// Returns the other [`ActionLayer`]s of the same actor entity as the given layer entity.
+    pub fn siblings(&self, layer: Entity) -> impl Iterator<Item = (Entity, &ActionLayer)> {
+        self.layers
+            .get(layer)
+            .ok()
+            .and_then(|(_, parent)| self.children.get(parent.0).ok())
+            .into_iter()
+            .flatten()
+            .copied()
+            .filter(move |&sibling| sibling != layer)
+            .filter_map(|sibling| self.get(sibling).map(|sibling_layer| (sibling, sibling_layer)))
+    }
+}
@@ -0,0 +1,241 @@
+use bevy::{ecs::component::ComponentId, platform::collections::HashMap, prelude::*};
+
+use crate::{
+    ecs::TargetedAction,
+    event::{ActionEndReason, OnActionEnded, OnActionInitiated},
+};
+
+/// An action made of ordered sub-action [`ComponentId`]s, or steps, that are performed one after another.
+///
+/// Sequences are identified by their own action [`ComponentId`], which can be picked like any other action.
+/// Register them in the [`ActionSequences`] [`Resource`].
+///
+/// - Initiating the sequence initiates its first step.
+/// - Completing a step initiates the next step, and completing the last step completes the sequence.
+/// - Cancelling a step cancels the sequence, and cancelling the sequence cancels the current step.
+///   Either way, the remaining steps are never initiated. Timing out and failing work the same way.
+///
+/// Steps are initiated and ended with the usual [`OnActionInitiated`] and [`OnActionEnded`] events,
+/// targeting the step's [`ComponentId`], so they're handled by regular action [`Observer`]s.
+/// Steps must not be sequences themselves, which [`ActionSequences::insert`] enforces.
+#[derive(Reflect)]
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+#[reflect(PartialEq, Debug, Default)]
+pub struct Sequence {
+    /// The action [`ComponentId`]s of the steps, in order.
+    pub steps: Vec<ComponentId>,
+}
+
+impl Sequence {
+    /// Creates a new [`Sequence`] with the given steps, in order.
+    #[must_use]
+    pub fn new(steps: impl IntoIterator<Item = ComponentId>) -> Self {
+        Self {
+            steps: steps.into_iter().collect(),
+        }
+    }
+}
+
+/// [`Resource`] holding all [`Sequence`]s, keyed by the action [`ComponentId`] that identifies them.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_observed_utility::{event::RequestAction, prelude::*};
+///
+/// # let mut app = App::new();
+/// # app.add_plugins(ObservedUtilityPlugins::TurnBased);
+/// # let mut world = app.world_mut();
+/// #[derive(Component)]
+/// pub struct Patrol;
+/// #[derive(Component, Default)]
+/// pub struct Walk;
+/// #[derive(Component, Default)]
+/// pub struct LookAround;
+/// #[derive(Component)]
+/// pub struct Idle;
+///
+/// let patrol = world.register_component::<Patrol>();
+/// let walk = world.register_component::<Walk>();
+/// let look_around = world.register_component::<LookAround>();
+/// let idle = world.register_component::<Idle>();
+///
+/// // Patrolling is walking, then looking around.
+/// world
+///     .resource_mut::<ActionSequences>()
+///     .insert(patrol, Sequence::new([walk, look_around]));
+///
+/// // Steps are handled like any other action.
+/// world.add_observer(on_action_initiated_insert_default::<Walk>);
+/// world.add_observer(on_action_initiated_insert_default::<LookAround>);
+/// world.add_observer(on_action_ended_remove::<Walk>);
+/// world.add_observer(on_action_ended_remove::<LookAround>);
+///
+/// let actor = world.spawn(Picker::new(idle)).id();
+/// world.trigger_targets(RequestAction::action(patrol), actor);
+/// world.flush();
+/// assert!(world.entity(actor).contains::<Walk>());
+///
+/// // Completing a step initiates the next one.
+/// world.trigger_targets(OnActionEnded::completed(walk), TargetedAction(actor, walk));
+/// world.flush();
+/// assert!(world.entity(actor).contains::<LookAround>());
+/// # assert!(!world.entity(actor).contains::<Walk>());
+/// ```
+#[derive(Resource, Reflect)]
+#[derive(Clone, PartialEq, Debug, Default)]
+#[reflect(Resource, PartialEq, Debug, Default)]
+pub struct ActionSequences {
+    sequences: HashMap<ComponentId, Sequence>,
+}
+
+impl ActionSequences {
+    /// Registers the [`Sequence`] identified by the given action [`ComponentId`], replacing any previous one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any step of the sequence is a sequence itself, including the sequence being registered,
+    /// or if the sequence is already a step of another registered sequence.
+    pub fn insert(&mut self, action: ComponentId, sequence: Sequence) -> Option<Sequence> {
+        assert!(
+            sequence
+                .steps
+                .iter()
+                .all(|&step| step != action && !self.contains(step)),
+            "the steps of sequence {action:?} must not be sequences themselves"
+        );
+        assert!(
+            self.sequences
+                .iter()
+                .all(|(&other, other_sequence)| other == action || !other_sequence.steps.contains(&action)),
+            "sequence {action:?} must not be a step of another sequence"
+        );
+        self.sequences.insert(action, sequence)
+    }
+
+    /// Unregisters the [`Sequence`] identified by the given action [`ComponentId`].
+    pub fn remove(&mut self, action: ComponentId) -> Option<Sequence> {
+        self.sequences.remove(&action)
+    }
+
+    /// Returns the [`Sequence`] identified by the given action [`ComponentId`], if any.
+    #[must_use]
+    pub fn get(&self, action: ComponentId) -> Option<&Sequence> {
+        self.sequences.get(&action)
+    }
+
+    /// Returns `true` if the given action [`ComponentId`] identifies a [`Sequence`].
+    #[must_use]
+    pub fn contains(&self, action: ComponentId) -> bool {
+        self.sequences.contains_key(&action)
+    }
+
+    /// [`Observer`] that initiates the first step of a [`Sequence`] when it is initiated.
+    pub fn on_initiated_start(
+        trigger: Trigger<OnActionInitiated>,
+        mut commands: Commands,
+        sequences: Res<ActionSequences>,
+    ) {
+        let actor = trigger.target();
+        let OnActionInitiated { action, target } = *trigger.event();
+        let Some(sequence) = sequences.get(action) else {
+            return;
+        };
+
+        match sequence.steps.first() {
+            Some(&step) => {
+                commands.entity(actor).insert(SequenceProgress {
+                    sequence: action,
+                    step: 0,
+                    target,
+                });
+                commands.trigger_targets(
+                    OnActionInitiated::new(step).with_target(target),
+                    TargetedAction(actor, step),
+                );
+            }
+            None => {
+                // Nothing to do, so we're done already
+                commands.trigger_targets(OnActionEnded::completed(action), TargetedAction(actor, action));
+            }
+        }
+    }
+
+    /// [`Observer`] that advances or ends the current [`Sequence`] when it or its current step ends.
+    pub fn on_ended_advance(
+        trigger: Trigger<OnActionEnded>,
+        mut commands: Commands,
+        sequences: Res<ActionSequences>,
+        mut actors: Query<&mut SequenceProgress>,
+    ) {
+        let actor = trigger.target();
+        let OnActionEnded { action, reason } = trigger.event().clone();
+        let Ok(mut progress) = actors.get_mut(actor) else {
+            return;
+        };
+        let sequence = progress.sequence;
+        let Some(steps) = sequences.get(sequence).map(|sequence| &sequence.steps) else {
+            // The sequence was unregistered while in progress
+            commands.entity(actor).remove::<SequenceProgress>();
+            return;
+        };
+        let current_step = steps.get(progress.step).copied();
+
+        if action == sequence {
+            // The sequence itself ended, e.g. because a different action was requested
+            commands.entity(actor).remove::<SequenceProgress>();
+            if let Some(step) = current_step {
+                commands.trigger_targets(OnActionEnded { action: step, reason }, TargetedAction(actor, step));
+            }
+            return;
+        }
+
+        if current_step != Some(action) {
+            // Some other action ended
+            return;
+        }
+
+        match reason {
+            ActionEndReason::Completed => {
+                progress.step += 1;
+                if let Some(&next_step) = steps.get(progress.step) {
+                    commands.trigger_targets(
+                        OnActionInitiated::new(next_step).with_target(progress.target),
+                        TargetedAction(actor, next_step),
+                    );
+                } else {
+                    // That was the last step
+                    commands.entity(actor).remove::<SequenceProgress>();
+                    commands.trigger_targets(OnActionEnded::completed(sequence), TargetedAction(actor, sequence));
+                }
+            }
+            ActionEndReason::Cancelled | ActionEndReason::TimedOut | ActionEndReason::Failed(_) => {
+                // Skip the remaining steps
+                commands.entity(actor).remove::<SequenceProgress>();
+                commands.trigger_targets(
+                    OnActionEnded {
+                        action: sequence,
+                        reason,
+                    },
+                    TargetedAction(actor, sequence),
+                );
+            }
+        }
+    }
+}
+
+/// [`Component`] for the progress of the [`Sequence`] an actor entity is currently performing.
+///
+/// This component is inserted when the sequence is initiated and removed when it ends.
+#[derive(Component, Reflect)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[reflect(Component, PartialEq, Debug)]
+pub struct SequenceProgress {
+    /// The action [`ComponentId`] of the [`Sequence`] being performed.
+    pub sequence: ComponentId,
+    /// The index of the step currently being performed.
+    pub step: usize,
+    /// The target [`Entity`] the sequence was initiated with, which is passed on to each step.
+    pub target: Option<Entity>,
+}
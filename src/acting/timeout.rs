@@ -0,0 +1,170 @@
+use std::time::Duration;
+
+use bevy::{ecs::component::ComponentId, platform::collections::HashMap, prelude::*};
+
+use crate::{
+    ecs::TargetedAction,
+    event::{OnActionEnded, OnActionInitiated},
+};
+
+/// [`Component`] for actor entities that limits how long any of their actions may take
+/// before they are ended with [`ActionEndReason::TimedOut`](crate::event::ActionEndReason::TimedOut).
+///
+/// Timeouts registered for specific actions in [`ActionTimeouts`] take precedence over this one.
+#[derive(Component, Reflect)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[reflect(Component, PartialEq, Debug, Default)]
+pub struct ActionTimeout(pub Duration);
+
+/// [`Resource`] holding how long actions may take before they are ended with
+/// [`ActionEndReason::TimedOut`](crate::event::ActionEndReason::TimedOut), keyed by action [`ComponentId`].
+///
+/// Actions without a registered timeout use the actor's [`ActionTimeout`], if any, or never time out.
+/// Timeouts are measured with the elapsed [`Time`] and checked every [`Update`].
+/// When an action times out, the [`ActionPlugin`](crate::acting::ActionPlugin) requests a new action for the actor.
+///
+/// # Example
+///
+/// ```rust
+/// use std::time::Duration;
+///
+/// use bevy::prelude::*;
+/// use bevy_observed_utility::{event::RequestAction, prelude::*};
+///
+/// # let mut app = App::new();
+/// # app.add_plugins(ObservedUtilityPlugins::TurnBased);
+/// app.init_resource::<Time>();
+/// #[derive(Component)]
+/// pub struct Wander;
+/// #[derive(Component)]
+/// pub struct Idle;
+///
+/// let world = app.world_mut();
+/// let wander = world.register_component::<Wander>();
+/// let idle = world.register_component::<Idle>();
+///
+/// world.resource_mut::<ActionTimeouts>().insert(wander, Duration::from_secs(5));
+///
+/// let actor = world.spawn(Picker::new(idle)).id();
+/// world.trigger_targets(RequestAction::action(wander), actor);
+/// world.flush();
+///
+/// // Wandering never ends by itself, so it's ended once it takes too long.
+/// world.resource_mut::<Time>().advance_by(Duration::from_secs(5));
+/// app.update();
+/// assert_eq!(Some(&CurrentAction(idle)), app.world().get::<CurrentAction>(actor));
+/// ```
+#[derive(Resource, Reflect)]
+#[derive(Clone, PartialEq, Debug, Default)]
+#[reflect(Resource, PartialEq, Debug, Default)]
+pub struct ActionTimeouts {
+    timeouts: HashMap<ComponentId, Duration>,
+}
+
+impl ActionTimeouts {
+    /// Sets the timeout of the given action [`ComponentId`], replacing any previous one.
+    pub fn insert(&mut self, action: ComponentId, timeout: Duration) -> Option<Duration> {
+        self.timeouts.insert(action, timeout)
+    }
+
+    /// Removes the timeout of the given action [`ComponentId`].
+    pub fn remove(&mut self, action: ComponentId) -> Option<Duration> {
+        self.timeouts.remove(&action)
+    }
+
+    /// Returns the timeout of the given action [`ComponentId`], if any.
+    #[must_use]
+    pub fn get(&self, action: ComponentId) -> Option<Duration> {
+        self.timeouts.get(&action).copied()
+    }
+
+    /// [`Observer`] that starts the timeout of an action when it is initiated, if it has one.
+    pub fn on_initiated_start(
+        trigger: Trigger<OnActionInitiated>,
+        mut commands: Commands,
+        timeouts: Res<ActionTimeouts>,
+        time: Option<Res<Time>>,
+        mut actors: Query<(Option<&ActionTimeout>, Option<&mut ActionDeadlines>)>,
+    ) {
+        let actor = trigger.target();
+        let action = trigger.event().action;
+        let (Some(time), Ok((actor_timeout, deadlines))) = (time, actors.get_mut(actor)) else {
+            return;
+        };
+        let Some(timeout) = timeouts
+            .get(action)
+            .or_else(|| actor_timeout.map(|actor_timeout| actor_timeout.0))
+        else {
+            return;
+        };
+
+        let deadline = time.elapsed() + timeout;
+        match deadlines {
+            Some(mut deadlines) => deadlines.set(action, deadline),
+            None => {
+                commands.entity(actor).insert(ActionDeadlines(vec![(action, deadline)]));
+            }
+        }
+    }
+
+    /// [`Observer`] that stops the timeout of an action when it ends.
+    pub fn on_ended_stop(trigger: Trigger<OnActionEnded>, mut actors: Query<&mut ActionDeadlines>) {
+        if let Ok(mut deadlines) = actors.get_mut(trigger.target()) {
+            deadlines.0.retain(|&(action, _)| action != trigger.event().action);
+        }
+    }
+
+    /// [`System`] that ends the actions whose timeouts have expired with
+    /// [`ActionEndReason::TimedOut`](crate::event::ActionEndReason::TimedOut).
+    pub fn time_out_expired(
+        mut commands: Commands,
+        time: Option<Res<Time>>,
+        mut actors: Query<(Entity, &mut ActionDeadlines)>,
+    ) {
+        let Some(time) = time else {
+            return;
+        };
+        let now = time.elapsed();
+
+        for (actor, mut deadlines) in &mut actors {
+            if deadlines.0.iter().all(|&(_, deadline)| deadline > now) {
+                // Avoid triggering change detection
+                continue;
+            }
+            deadlines.0.retain(|&(action, deadline)| {
+                if deadline > now {
+                    return true;
+                }
+                commands.trigger_targets(OnActionEnded::timed_out(action), TargetedAction(actor, action));
+                false
+            });
+        }
+    }
+}
+
+/// [`Component`] tracking when the running actions of an actor entity time out, as elapsed [`Time`].
+///
+/// This is maintained by [`ActionTimeouts`], see it for more information.
+#[derive(Component, Reflect)]
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+#[reflect(Component, PartialEq, Debug, Default)]
+pub struct ActionDeadlines(Vec<(ComponentId, Duration)>);
+
+impl ActionDeadlines {
+    /// Returns the elapsed [`Time`] at which the given action times out, if it's running and has a timeout.
+    #[must_use]
+    pub fn get(&self, action: ComponentId) -> Option<Duration> {
+        self.0
+            .iter()
+            .find(|&&(other, _)| other == action)
+            .map(|&(_, deadline)| deadline)
+    }
+
+    /// Sets the deadline of the given action, e.g. when it's initiated again.
+    fn set(&mut self, action: ComponentId, deadline: Duration) {
+        match self.0.iter_mut().find(|(other, _)| *other == action) {
+            Some((_, existing)) => *existing = deadline,
+            None => self.0.push((action, deadline)),
+        }
+    }
+}
@@ -0,0 +1,475 @@
+//! Utility trees described in asset files (requires `asset` feature), so that they can be changed without recompiling.
+//!
+//! A [`UtilityTree`] describes the [`Score`] entity tree of each action an actor can pick,
+//! as well as how the actor picks between them.
+//! They're loaded from `.utility.ron` files by the [`UtilityTreeLoader`].
+//!
+//! Insert a [`UtilityTreeHandle`] onto an actor entity to spawn the tree onto it once it's loaded.
+//! The [`UtilityTreePlugin`] must be added for this, and requires Bevy's `AssetPlugin`.
+//!
+//! When a tree asset is modified, e.g. by hot reloading, or an actor's [`UtilityTreeHandle`] is changed,
+//! the tree is respawned onto the actor entity. See [`UtilityTree::spawn`] for how its current action is handled.
+//! If the new tree fails to spawn, the previous one is kept.
+//!
+//! # Example
+//!
+//! Given these types, registered with `app.register_type::<T>()`:
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//!
+//! /// Scored with `score_ancestor`.
+//! #[derive(Component, Reflect, Default)]
+//! #[reflect(Component, Default)]
+//! pub struct Thirsty;
+//!
+//! /// Actions must be reflected as components, or already registered with `World::register_component`.
+//! #[derive(Component, Reflect)]
+//! #[reflect(Component)]
+//! pub struct Drink;
+//! #[derive(Component, Reflect)]
+//! #[reflect(Component)]
+//! pub struct Idle;
+//! ```
+//!
+//! A tree that drinks when thirsty might look like this:
+//!
+//! ```ron
+//! (
+//!     picker: FirstToScore(0.5),
+//!     default_action: "Idle",
+//!     choices: [
+//!         (
+//!             action: "Drink",
+//!             score: (
+//!                 scorer: Evaluated(Power(power: 2., a: (0., 0.), b: (1., 1.))),
+//!                 children: [
+//!                     (scorer: Custom("Thirsty")),
+//!                 ],
+//!             ),
+//!         ),
+//!     ],
+//! )
+//! ```
+//!
+//! Types can be referred to by their full type path (e.g. `"my_game::ai::Drink"`),
+//! or by their short type path (e.g. `"Drink"`) if it's unambiguous.
+//!
+//! [`Score`]: crate::scoring::Score
+
+use bevy::prelude::*;
+
+mod loader;
+mod tree;
+
+pub use loader::*;
+pub use tree::*;
+
+/// [`Plugin`] for loading [`UtilityTree`]s and spawning them onto actor entities with a [`UtilityTreeHandle`].
+///
+/// This plugin is not included in [`ObservedUtilityPlugins`](crate::ObservedUtilityPlugins),
+/// since it requires Bevy's `AssetPlugin`.
+pub struct UtilityTreePlugin;
+
+impl Plugin for UtilityTreePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<UtilityTree>().init_asset_loader::<UtilityTreeLoader>();
+
+        app.add_systems(
+            PreUpdate,
+            (Self::mark_outdated_utility_trees, Self::spawn_utility_trees).chain(),
+        );
+
+        app.register_type::<UtilityTreeHandle>()
+            .register_type::<UtilityTreeInstance>()
+            .register_type::<UtilityTreeOutdated>();
+    }
+}
+
+impl UtilityTreePlugin {
+    /// [`System`] that marks actor entities whose [`UtilityTree`] was modified, or whose [`UtilityTreeHandle`]
+    /// was changed, as [`UtilityTreeOutdated`], so that they're respawned by [`UtilityTreePlugin::spawn_utility_trees`].
+    pub fn mark_outdated_utility_trees(
+        mut commands: Commands,
+        mut events: EventReader<AssetEvent<UtilityTree>>,
+        actors: Query<(Entity, Ref<UtilityTreeHandle>), With<UtilityTreeInstance>>,
+    ) {
+        let modified: Vec<AssetId<UtilityTree>> = events
+            .read()
+            .filter_map(|event| match *event {
+                AssetEvent::Modified { id } => Some(id),
+                _ => None,
+            })
+            .collect();
+
+        for (actor, handle) in actors.iter() {
+            if handle.is_changed() || modified.contains(&handle.0.id()) {
+                commands.entity(actor).insert(UtilityTreeOutdated);
+            }
+        }
+    }
+
+    /// [`System`] that spawns loaded [`UtilityTree`]s onto the actor entities with their [`UtilityTreeHandle`],
+    /// and inserts a [`UtilityTreeInstance`] to record what was spawned.
+    ///
+    /// For [`UtilityTreeOutdated`] actor entities, the [`Score`] entities of the previous tree are despawned
+    /// once the new tree has spawned. The actor entities themselves are left untouched.
+    ///
+    /// Trees that fail to spawn are logged and left unspawned, keeping the previous tree and [`Picker`] choices.
+    ///
+    /// [`Score`]: crate::scoring::Score
+    /// [`Picker`]: crate::picking::Picker
+    pub fn spawn_utility_trees(
+        world: &mut World,
+        actors: &mut QueryState<(Entity, &UtilityTreeHandle), UnspawnedOrOutdated>,
+    ) {
+        let pending: Vec<(Entity, Handle<UtilityTree>)> = actors
+            .iter(world)
+            .map(|(actor, handle)| (actor, handle.0.clone()))
+            .collect();
+        if pending.is_empty() {
+            return;
+        }
+
+        world.resource_scope(|world, trees: Mut<Assets<UtilityTree>>| {
+            for (actor, handle) in pending {
+                let Some(tree) = trees.get(&handle) else {
+                    // Not loaded yet
+                    continue;
+                };
+
+                // Nothing is spawned or changed if the tree is invalid
+                let instance = match tree.spawn(world, actor) {
+                    Ok(instance) => instance,
+                    Err(error) => {
+                        error!("Failed to spawn utility tree onto {actor}: {error}");
+                        let mut actor = world.entity_mut(actor);
+                        actor.remove::<UtilityTreeOutdated>();
+                        if !actor.contains::<UtilityTreeInstance>() {
+                            actor.insert(UtilityTreeInstance::default());
+                        }
+                        continue;
+                    }
+                };
+
+                let previous = world.entity_mut(actor).take::<UtilityTreeInstance>();
+                for score in previous.into_iter().flat_map(|previous| previous.scores) {
+                    if let Ok(score) = world.get_entity_mut(score) {
+                        score.despawn();
+                    }
+                }
+                world.entity_mut(actor).remove::<UtilityTreeOutdated>().insert(instance);
+            }
+        });
+    }
+}
+
+/// [`QueryFilter`] for actor entities whose [`UtilityTree`] needs to be spawned.
+///
+/// [`QueryFilter`]: bevy::ecs::query::QueryFilter
+type UnspawnedOrOutdated = Or<(Without<UtilityTreeInstance>, With<UtilityTreeOutdated>)>;
+
+/// [`Component`] for actor entities whose [`Score`] entity tree and [`Picker`] are spawned from a [`UtilityTree`].
+///
+/// [`Score`]: crate::scoring::Score
+/// [`Picker`]: crate::picking::Picker
+#[derive(Component, Reflect)]
+#[derive(Clone, PartialEq, Debug, Default)]
+#[reflect(Component, PartialEq, Debug, Default)]
+pub struct UtilityTreeHandle(pub Handle<UtilityTree>);
+
+/// Marker [`Component`] for actor entities whose spawned [`UtilityTree`] is outdated,
+/// because the tree asset was modified or their [`UtilityTreeHandle`] was changed.
+///
+/// This is inserted by [`UtilityTreePlugin::mark_outdated_utility_trees`],
+/// and removed once [`UtilityTreePlugin::spawn_utility_trees`] has tried respawning the tree.
+#[derive(Component, Reflect)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[reflect(Component, PartialEq, Debug, Default)]
+pub struct UtilityTreeOutdated;
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+    use bevy::{asset::AssetPlugin, prelude::*};
+
+    use crate::{
+        ObservedUtilityPlugins,
+        acting::{CurrentAction, CurrentTarget},
+        asset::{
+            ChoiceNode, EvaluatorKind, PickerKind, ScoreNode, ScorerKind, UtilityTree, UtilityTreeError,
+            UtilityTreeHandle, UtilityTreeInstance, UtilityTreeOutdated, UtilityTreePlugin, ron_options,
+        },
+        event::{ActionEndReason, OnActionEnded, RequestAction, RunPicking, RunScoring},
+        picking::{FirstToScore, Picker},
+        scoring::{FixedScore, Score, Scorers, Weighted},
+    };
+
+    #[derive(Component, Reflect, Default)]
+    #[reflect(Component, Default)]
+    struct Thirsty;
+
+    #[derive(Component, Reflect)]
+    #[reflect(Component)]
+    struct Drink;
+
+    /// Not reflected as a component, so it has to be registered before spawning.
+    #[derive(Component, Reflect)]
+    struct Idle;
+
+    const TREE: &str = r#"(
+        picker: FirstToScore(0.5),
+        default_action: "Idle",
+        choices: [
+            (
+                action: "bevy_observed_utility::asset::tests::Drink",
+                score: (
+                    scorer: Evaluated(Power(power: 2., a: (0., 0.), b: (1., 1.))),
+                    children: [
+                        (scorer: Fixed(0.8), weight: 0.5),
+                    ],
+                ),
+            ),
+            (
+                action: "Idle",
+                score: (scorer: Custom("Thirsty")),
+            ),
+        ],
+    )"#;
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_plugins((
+            TaskPoolPlugin::default(),
+            AssetPlugin::default(),
+            ObservedUtilityPlugins::TurnBased,
+            UtilityTreePlugin,
+        ));
+        app.register_type::<Thirsty>()
+            .register_type::<Drink>()
+            .register_type::<Idle>();
+        app.world_mut().register_component::<Idle>();
+        app
+    }
+
+    #[test]
+    fn parse() {
+        let tree: UtilityTree = ron_options().from_str(TREE).unwrap();
+
+        assert_eq!(PickerKind::FirstToScore(0.5), tree.picker);
+        assert_eq!("Idle", tree.default_action);
+        assert_eq!(
+            ChoiceNode {
+                action: "bevy_observed_utility::asset::tests::Drink".to_owned(),
+                score: ScoreNode {
+                    scorer: ScorerKind::Evaluated(EvaluatorKind::Power {
+                        power: 2.,
+                        a: (0., 0.),
+                        b: (1., 1.),
+                    }),
+                    weight: None,
+                    children: vec![ScoreNode {
+                        scorer: ScorerKind::Fixed(0.8),
+                        weight: Some(0.5),
+                        children: Vec::new(),
+                    }],
+                },
+            },
+            tree.choices[0]
+        );
+    }
+
+    #[test]
+    fn spawn() {
+        let mut app = app();
+        let tree: UtilityTree = ron_options().from_str(TREE).unwrap();
+        let handle = app.world_mut().resource_mut::<Assets<UtilityTree>>().add(tree);
+        let actor = app.world_mut().spawn(UtilityTreeHandle(handle)).id();
+
+        app.update();
+
+        let world = app.world_mut();
+        let drink = world.register_component::<Drink>();
+        let idle = world.register_component::<Idle>();
+
+        let instance = world.get::<UtilityTreeInstance>(actor).unwrap().clone();
+        let [drinking, idling] = instance.scores[..] else {
+            panic!("expected two choices, got {:?}", instance.scores);
+        };
+        let picker = world.get::<Picker>(actor).unwrap();
+        assert_eq!(idle, picker.default);
+        assert_eq!(Some(&drink), picker.choices.get(&drinking));
+        assert_eq!(Some(&idle), picker.choices.get(&idling));
+        assert_eq!(Some(&FirstToScore::new(0.5)), world.get::<FirstToScore>(actor));
+        assert_eq!(&[drinking, idling], &world.get::<Scorers>(actor).unwrap()[..]);
+
+        let &[fixed] = &world.get::<Scorers>(drinking).unwrap()[..] else {
+            panic!("expected one child");
+        };
+        assert_eq!(Some(&FixedScore::new(0.8)), world.get::<FixedScore>(fixed));
+        assert_eq!(Some(&Weighted::new(0.5)), world.get::<Weighted>(fixed));
+        assert!(world.entity(idling).contains::<Thirsty>());
+
+        world.trigger(RunScoring);
+        world.trigger_targets(RunPicking, actor);
+        world.flush();
+
+        assert_relative_eq!(0.64, world.get::<Score>(drinking).unwrap().get());
+        assert_eq!(drink, world.get::<Picker>(actor).unwrap().picked);
+    }
+
+    #[test]
+    fn spawn_unknown_type() {
+        let mut app = app();
+        let tree = UtilityTree {
+            picker: PickerKind::Highest,
+            default_action: "Idle".to_owned(),
+            choices: vec![ChoiceNode {
+                action: "Sleep".to_owned(),
+                score: ScoreNode {
+                    scorer: ScorerKind::Fixed(1.),
+                    weight: None,
+                    children: Vec::new(),
+                },
+            }],
+        };
+        let world = app.world_mut();
+        let actor = world.spawn_empty().id();
+
+        let result = tree.spawn(world, actor);
+
+        assert!(matches!(result, Err(UtilityTreeError::UnknownType(type_path)) if type_path == "Sleep"));
+        assert!(world.get::<Picker>(actor).is_none());
+        assert_eq!(0, world.query::<&Score>().iter(world).count());
+    }
+
+    #[test]
+    fn reload() {
+        #[derive(Resource, Default)]
+        struct Ended(Vec<OnActionEnded>);
+
+        let mut app = app();
+        app.init_resource::<Ended>();
+        app.add_observer(|trigger: Trigger<OnActionEnded>, mut ended: ResMut<Ended>| {
+            ended.0.push(trigger.event().clone());
+        });
+
+        let tree: UtilityTree = ron_options().from_str(TREE).unwrap();
+        let handle = app.world_mut().resource_mut::<Assets<UtilityTree>>().add(tree);
+        let actor = app.world_mut().spawn(UtilityTreeHandle(handle.clone())).id();
+        app.update();
+
+        let drink = app.world_mut().register_component::<Drink>();
+        let idle = app.world_mut().register_component::<Idle>();
+        let request = RequestAction::action(drink);
+        app.world_mut().trigger_targets(request, actor);
+        app.world_mut().flush();
+        let old_scores = app.world().get::<UtilityTreeInstance>(actor).unwrap().scores.clone();
+
+        // Change a score, keeping the current action
+        let reload = |app: &mut App, edit: &dyn Fn(&mut UtilityTree)| {
+            edit(
+                app.world_mut()
+                    .resource_mut::<Assets<UtilityTree>>()
+                    .get_mut(&handle)
+                    .unwrap(),
+            );
+            app.update();
+            app.update();
+        };
+        reload(&mut app, &|tree| tree.choices[1].score.scorer = ScorerKind::Fixed(0.1));
+
+        let world = app.world();
+        let scores = world.get::<UtilityTreeInstance>(actor).unwrap().scores.clone();
+        assert_eq!(2, scores.len());
+        for old_score in &old_scores {
+            assert!(world.get_entity(*old_score).is_err());
+        }
+        assert_eq!(&scores[..], &world.get::<Scorers>(actor).unwrap()[..]);
+        let picker = world.get::<Picker>(actor).unwrap();
+        assert_eq!(Some(&drink), picker.choices.get(&scores[0]));
+        assert_eq!(Some(&idle), picker.choices.get(&scores[1]));
+        assert_eq!(Some(&FixedScore::new(0.1)), world.get::<FixedScore>(scores[1]));
+        assert_eq!(Some(&CurrentAction(drink)), world.get::<CurrentAction>(actor));
+        assert!(world.resource::<Ended>().0.is_empty());
+
+        // Remove the current action
+        reload(&mut app, &|tree| {
+            tree.choices.remove(0);
+        });
+
+        let world = app.world();
+        let scores = world.get::<UtilityTreeInstance>(actor).unwrap().scores.clone();
+        assert_eq!(1, scores.len());
+        assert_eq!(&scores[..], &world.get::<Scorers>(actor).unwrap()[..]);
+        assert_eq!(None, world.get::<CurrentAction>(actor));
+        assert_eq!(None, world.get::<CurrentTarget>(actor));
+        assert_eq!(
+            vec![OnActionEnded {
+                action: drink,
+                reason: ActionEndReason::Cancelled
+            }],
+            world.resource::<Ended>().0
+        );
+    }
+
+    #[test]
+    fn reload_invalid() {
+        let mut app = app();
+        let tree: UtilityTree = ron_options().from_str(TREE).unwrap();
+        let handle = app.world_mut().resource_mut::<Assets<UtilityTree>>().add(tree);
+        let actor = app.world_mut().spawn(UtilityTreeHandle(handle.clone())).id();
+        app.update();
+
+        let old_instance = app.world().get::<UtilityTreeInstance>(actor).unwrap().clone();
+        let old_picker = app.world().get::<Picker>(actor).unwrap().clone();
+
+        // Reference an action that doesn't exist
+        app.world_mut()
+            .resource_mut::<Assets<UtilityTree>>()
+            .get_mut(&handle)
+            .unwrap()
+            .choices[0]
+            .action = "Sleep".to_owned();
+        app.update();
+        app.update();
+
+        // The previous tree is kept
+        let world = app.world();
+        assert_eq!(Some(&old_instance), world.get::<UtilityTreeInstance>(actor));
+        assert_eq!(Some(&old_picker), world.get::<Picker>(actor));
+        for score in old_picker.choices.keys() {
+            assert!(world.get::<Score>(*score).is_some());
+        }
+        assert!(!world.entity(actor).contains::<UtilityTreeOutdated>());
+    }
+
+    #[test]
+    fn handle_changed() {
+        let mut app = app();
+        let tree: UtilityTree = ron_options().from_str(TREE).unwrap();
+        let mut other_tree = tree.clone();
+        other_tree.choices.truncate(1);
+        let handle = app.world_mut().resource_mut::<Assets<UtilityTree>>().add(tree);
+        let other_handle = app.world_mut().resource_mut::<Assets<UtilityTree>>().add(other_tree);
+        let actor = app.world_mut().spawn(UtilityTreeHandle(handle)).id();
+        app.update();
+
+        let old_scores = app.world().get::<UtilityTreeInstance>(actor).unwrap().scores.clone();
+        assert_eq!(2, old_scores.len());
+
+        app.world_mut()
+            .entity_mut(actor)
+            .insert(UtilityTreeHandle(other_handle));
+        app.update();
+
+        let world = app.world();
+        let scores = world.get::<UtilityTreeInstance>(actor).unwrap().scores.clone();
+        assert_eq!(1, scores.len());
+        for old_score in &old_scores {
+            assert!(world.get_entity(*old_score).is_err());
+        }
+        assert_eq!(&scores[..], &world.get::<Scorers>(actor).unwrap()[..]);
+        assert_eq!(1, world.get::<Picker>(actor).unwrap().choices.len());
+    }
+}
@@ -0,0 +1,66 @@
+use bevy::{
+    asset::{AssetLoader, LoadContext, io::Reader},
+    prelude::*,
+    reflect::TypeRegistryArc,
+};
+use ron::extensions::Extensions;
+use thiserror::Error;
+
+use crate::asset::{UtilityTree, UtilityTreeError};
+
+/// [`AssetLoader`] for [`UtilityTree`]s stored as RON in `.utility.ron` files.
+///
+/// Loaded trees are [validated](UtilityTree::validate) against the [`AppTypeRegistry`].
+pub struct UtilityTreeLoader {
+    type_registry: TypeRegistryArc,
+}
+
+impl FromWorld for UtilityTreeLoader {
+    fn from_world(world: &mut World) -> Self {
+        Self {
+            type_registry: world.resource::<AppTypeRegistry>().0.clone(),
+        }
+    }
+}
+
+impl AssetLoader for UtilityTreeLoader {
+    type Asset = UtilityTree;
+    type Settings = ();
+    type Error = UtilityTreeLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let tree: UtilityTree = ron_options().from_bytes(&bytes)?;
+        tree.validate(&self.type_registry.read())?;
+        Ok(tree)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["utility.ron"]
+    }
+}
+
+/// The RON options used to parse [`UtilityTree`]s, which allow omitting `Some(..)` around optional values.
+pub(crate) fn ron_options() -> ron::Options {
+    ron::Options::default().with_default_extension(Extensions::IMPLICIT_SOME)
+}
+
+/// An error that can occur when loading a [`UtilityTree`].
+#[derive(Error, Debug)]
+pub enum UtilityTreeLoaderError {
+    /// The file couldn't be read.
+    #[error("could not read utility tree: {0}")]
+    Io(#[from] std::io::Error),
+    /// The file isn't a valid RON utility tree.
+    #[error("could not parse utility tree: {0}")]
+    Ron(#[from] ron::error::SpannedError),
+    /// The tree references types that aren't registered.
+    #[error("invalid utility tree: {0}")]
+    Invalid(#[from] UtilityTreeError),
+}
@@ -0,0 +1,410 @@
+use bevy::{
+    ecs::{component::ComponentId, reflect::ReflectComponent},
+    prelude::*,
+    reflect::{TypeRegistration, TypeRegistry},
+};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+#[cfg(feature = "rand")]
+use crate::picking::{PickRandom, PickWeightedRandom};
+use crate::{
+    acting::{CurrentAction, CurrentTarget},
+    ecs::TargetedAction,
+    event::OnActionEnded,
+    picking::{FirstToScore, Highest, Picker},
+    scoring::{
+        AllOrNothing, CubicKeyframe, CubicSplineEvaluator, Evaluated, ExponentialEvaluator, FixedScore,
+        LinearEvaluator, LogarithmicEvaluator, Measured, PiecewiseLinearEvaluator, PowerEvaluator, Product, Score,
+        ScoreOf, SigmoidEvaluator, Sum, Weighted, WeightedMax, WeightedProduct, WeightedRMS, WeightedSum, Winning,
+    },
+};
+
+/// [`Asset`] describing the [`Score`] hierarchy and [`Picker`] of an actor entity.
+///
+/// Actions are identified by the type path of their [`Component`] (or its short type path, if unambiguous),
+/// which must be registered in the [`AppTypeRegistry`].
+///
+/// See the [module docs](crate::asset) for the file format.
+#[derive(Asset, TypePath, Serialize, Deserialize)]
+#[derive(Clone, PartialEq, Debug)]
+pub struct UtilityTree {
+    /// How the actor picks an action.
+    pub picker: PickerKind,
+    /// The type path of the default action.
+    pub default_action: String,
+    /// The actions to pick from, along with the [`Score`] entity tree they're picked with.
+    #[serde(default)]
+    pub choices: Vec<ChoiceNode>,
+}
+
+impl UtilityTree {
+    /// Checks that all of the types referenced by the tree are registered, without spawning anything.
+    pub fn validate(&self, registry: &TypeRegistry) -> Result<(), UtilityTreeError> {
+        registration(registry, &self.default_action)?;
+        for choice in &self.choices {
+            registration(registry, &choice.action)?;
+            choice.score.validate(registry)?;
+        }
+        Ok(())
+    }
+
+    /// Spawns the [`Score`] entity tree of each choice as [`ScoreOf`] the actor entity,
+    /// and inserts the [`Picker`] and the picker kind's [`Component`] onto it.
+    ///
+    /// If the actor entity already has a [`Picker`], e.g. when reloading the tree,
+    /// its picked action is kept if it's still in the tree.
+    /// If its [`CurrentAction`] is no longer in the tree, it's cancelled with [`OnActionEnded`] and removed,
+    /// along with its [`CurrentTarget`].
+    ///
+    /// Nothing is spawned if the tree references a type that isn't registered.
+    pub fn spawn(&self, world: &mut World, actor: Entity) -> Result<UtilityTreeInstance, UtilityTreeError> {
+        let type_registry = world.resource::<AppTypeRegistry>().clone();
+        let registry = type_registry.read();
+        self.validate(&registry)?;
+
+        // Resolve all actions before spawning anything
+        let default = action_id(world, &registry, &self.default_action)?;
+        let actions = self
+            .choices
+            .iter()
+            .map(|choice| action_id(world, &registry, &choice.action))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut picker = Picker::new(default);
+        let mut scores = Vec::with_capacity(self.choices.len());
+        for (choice, action) in self.choices.iter().zip(actions) {
+            let score = choice.score.spawn(world, &registry);
+            picker = picker.with(score, action);
+            scores.push(score);
+        }
+
+        drop(registry);
+
+        // Keep the picked action if it still exists
+        let picked = world.get::<Picker>(actor).map(|previous| previous.picked);
+        if let Some(picked) = picked.filter(|&picked| picker.contains(picked)) {
+            picker.picked = picked;
+        }
+
+        let removed_action = world
+            .get::<CurrentAction>(actor)
+            .map(|ca| ca.0)
+            .filter(|&current_action| !picker.contains(current_action));
+
+        let mut entity = world.entity_mut(actor);
+        entity.insert(picker).add_related::<ScoreOf>(&scores);
+        self.picker.insert(&mut entity);
+
+        // Cancel the current action if it no longer exists
+        if let Some(current_action) = removed_action {
+            world.trigger_targets(
+                OnActionEnded::cancelled(current_action),
+                TargetedAction(actor, current_action),
+            );
+            world.entity_mut(actor).remove::<(CurrentAction, CurrentTarget)>();
+        }
+
+        Ok(UtilityTreeInstance { scores })
+    }
+}
+
+/// An action to pick in a [`UtilityTree`], along with the [`Score`] entity tree it's picked with.
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, PartialEq, Debug)]
+pub struct ChoiceNode {
+    /// The type path of the action.
+    pub action: String,
+    /// The root of the [`Score`] entity tree.
+    pub score: ScoreNode,
+}
+
+/// A [`Score`] entity in a [`UtilityTree`].
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, PartialEq, Debug)]
+pub struct ScoreNode {
+    /// How the entity calculates its [`Score`].
+    pub scorer: ScorerKind,
+    /// The [`Weighted`] weight of the entity, if any.
+    #[serde(default)]
+    pub weight: Option<f32>,
+    /// The child [`Score`] entities.
+    #[serde(default)]
+    pub children: Vec<ScoreNode>,
+}
+
+impl ScoreNode {
+    /// Checks that all of the types referenced by the node and its children are registered.
+    fn validate(&self, registry: &TypeRegistry) -> Result<(), UtilityTreeError> {
+        if let ScorerKind::Custom(type_path) = &self.scorer {
+            let registration = registration(registry, type_path)?;
+            if !registration.contains::<ReflectComponent>() {
+                return Err(UtilityTreeError::NotAComponent(type_path.clone()));
+            }
+            if !registration.contains::<ReflectDefault>() {
+                return Err(UtilityTreeError::NoDefault(type_path.clone()));
+            }
+        }
+        self.children.iter().try_for_each(|child| child.validate(registry))
+    }
+
+    /// Spawns the [`Score`] entity and its children, assuming the node was validated.
+    fn spawn(&self, world: &mut World, registry: &TypeRegistry) -> Entity {
+        let mut entity = world.spawn(Score::default());
+        match &self.scorer {
+            ScorerKind::Fixed(value) => entity.insert(FixedScore::new(*value)),
+            ScorerKind::AllOrNothing(threshold) => entity.insert(AllOrNothing::new(*threshold)),
+            ScorerKind::Sum(threshold) => entity.insert(Sum::new(*threshold)),
+            ScorerKind::Product {
+                threshold,
+                compensation,
+            } => entity.insert(Product::new(*threshold).with_compensation(*compensation)),
+            ScorerKind::Winning(threshold) => entity.insert(Winning::new(*threshold)),
+            ScorerKind::Evaluated(evaluator) => entity.insert(evaluator.evaluated()),
+            ScorerKind::Measured(measure) => entity.insert(measure.measured()),
+            ScorerKind::Custom(type_path) => {
+                let registration = registration(registry, type_path).ok();
+                let component = registration.and_then(TypeRegistration::data::<ReflectComponent>);
+                let default = registration.and_then(TypeRegistration::data::<ReflectDefault>);
+                if let (Some(component), Some(default)) = (component, default) {
+                    component.insert(&mut entity, default.default().as_partial_reflect(), registry);
+                }
+                &mut entity
+            }
+        };
+        if let Some(weight) = self.weight {
+            entity.insert(Weighted::new(weight));
+        }
+
+        let entity = entity.id();
+        for child in &self.children {
+            let child = child.spawn(world, registry);
+            world.entity_mut(child).insert(ScoreOf(entity));
+        }
+        entity
+    }
+}
+
+/// How a [`ScoreNode`] calculates its [`Score`].
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, PartialEq, Debug)]
+pub enum ScorerKind {
+    /// [`FixedScore`] with the given value.
+    Fixed(f32),
+    /// [`AllOrNothing`] with the given threshold.
+    AllOrNothing(f32),
+    /// [`Sum`] with the given threshold.
+    Sum(f32),
+    /// [`Product`] with the given threshold, optionally with compensation.
+    Product {
+        /// The threshold for the product of child scores to be considered a success.
+        threshold: f32,
+        /// Whether to use compensation to prevent the product from being too low.
+        #[serde(default)]
+        compensation: bool,
+    },
+    /// [`Winning`] with the given threshold.
+    Winning(f32),
+    /// [`Evaluated`] with the given evaluator.
+    Evaluated(EvaluatorKind),
+    /// [`Measured`] with the given measure.
+    Measured(MeasureKind),
+    /// The [`Default`] value of the [`Component`] with the given type path,
+    /// e.g. a marker component scored with [`score_ancestor`](crate::scoring::score_ancestor).
+    ///
+    /// The component must be registered with `#[reflect(Component, Default)]`.
+    Custom(String),
+}
+
+/// An [`Evaluator`](crate::scoring::Evaluator) for [`ScorerKind::Evaluated`].
+///
+/// Points are `(x, y)` pairs.
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, PartialEq, Debug)]
+pub enum EvaluatorKind {
+    /// [`LinearEvaluator`] between the two given points.
+    Linear {
+        /// The start point.
+        a: (f32, f32),
+        /// The end point.
+        b: (f32, f32),
+    },
+    /// [`PowerEvaluator`] with the given power between the two given points.
+    Power {
+        /// The power to raise the value to.
+        power: f32,
+        /// The start point.
+        a: (f32, f32),
+        /// The end point.
+        b: (f32, f32),
+    },
+    /// [`SigmoidEvaluator`] with the given steepness between the two given points.
+    Sigmoid {
+        /// The steepness of the curve.
+        k: f32,
+        /// The start point.
+        a: (f32, f32),
+        /// The end point.
+        b: (f32, f32),
+    },
+    /// [`ExponentialEvaluator`] with the given steepness between the two given points.
+    Exponential {
+        /// The steepness of the curve.
+        k: f32,
+        /// The start point.
+        a: (f32, f32),
+        /// The end point.
+        b: (f32, f32),
+    },
+    /// [`LogarithmicEvaluator`] with the given steepness between the two given points.
+    Logarithmic {
+        /// The steepness of the curve.
+        k: f32,
+        /// The start point.
+        a: (f32, f32),
+        /// The end point.
+        b: (f32, f32),
+    },
+    /// [`PiecewiseLinearEvaluator`] through the given keyframe points.
+    PiecewiseLinear(Vec<(f32, f32)>),
+    /// [`CubicSplineEvaluator`] through the given `(x, y, tangent)` keyframes.
+    CubicSpline(Vec<(f32, f32, f32)>),
+}
+
+impl EvaluatorKind {
+    /// Creates an [`Evaluated`] using this evaluator.
+    #[must_use]
+    pub fn evaluated(&self) -> Evaluated {
+        match self.clone() {
+            Self::Linear { a, b } => Evaluated::new(LinearEvaluator::new(a.into(), b.into())),
+            Self::Power { power, a, b } => Evaluated::new(PowerEvaluator::new(power, a.into(), b.into())),
+            Self::Sigmoid { k, a, b } => Evaluated::new(SigmoidEvaluator::new(k, a.into(), b.into())),
+            Self::Exponential { k, a, b } => Evaluated::new(ExponentialEvaluator::new(k, a.into(), b.into())),
+            Self::Logarithmic { k, a, b } => Evaluated::new(LogarithmicEvaluator::new(k, a.into(), b.into())),
+            Self::PiecewiseLinear(keyframes) => {
+                Evaluated::new(PiecewiseLinearEvaluator::new(keyframes.into_iter().map(Vec2::from)))
+            }
+            Self::CubicSpline(keyframes) => Evaluated::new(CubicSplineEvaluator::new(
+                keyframes
+                    .into_iter()
+                    .map(|(x, y, tangent)| CubicKeyframe::new(Vec2::new(x, y), tangent)),
+            )),
+        }
+    }
+}
+
+/// A [`Measure`](crate::scoring::Measure) for [`ScorerKind::Measured`].
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MeasureKind {
+    /// [`WeightedSum`].
+    WeightedSum,
+    /// [`WeightedProduct`].
+    WeightedProduct,
+    /// [`WeightedMax`].
+    WeightedMax,
+    /// [`WeightedRMS`].
+    WeightedRMS,
+}
+
+impl MeasureKind {
+    /// Creates a [`Measured`] using this measure.
+    #[must_use]
+    pub fn measured(&self) -> Measured {
+        match self {
+            Self::WeightedSum => Measured::new(WeightedSum),
+            Self::WeightedProduct => Measured::new(WeightedProduct),
+            Self::WeightedMax => Measured::new(WeightedMax),
+            Self::WeightedRMS => Measured::new(WeightedRMS),
+        }
+    }
+}
+
+/// How the actor of a [`UtilityTree`] picks an action.
+#[derive(Serialize, Deserialize)]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum PickerKind {
+    /// [`Highest`].
+    Highest,
+    /// [`FirstToScore`] with the given threshold.
+    FirstToScore(f32),
+    /// [`PickRandom`] (requires `rand` feature), seeded from the OS.
+    #[cfg(feature = "rand")]
+    Random,
+    /// [`PickWeightedRandom`] (requires `rand` feature), seeded from the OS, with an optional temperature.
+    #[cfg(feature = "rand")]
+    WeightedRandom {
+        /// The softmax temperature, or `None` to weight choices by their scores directly.
+        #[serde(default)]
+        temperature: Option<f32>,
+    },
+}
+
+impl PickerKind {
+    /// Inserts the picker [`Component`] onto the actor entity, replacing any other picker kind.
+    pub fn insert(&self, actor: &mut EntityWorldMut) {
+        actor.remove::<(Highest, FirstToScore)>();
+        #[cfg(feature = "rand")]
+        actor.remove::<(PickRandom, PickWeightedRandom)>();
+
+        match *self {
+            Self::Highest => actor.insert(Highest),
+            Self::FirstToScore(threshold) => actor.insert(FirstToScore::new(threshold)),
+            #[cfg(feature = "rand")]
+            Self::Random => {
+                use rand::{SeedableRng, rngs::StdRng};
+                actor.insert(PickRandom::new(StdRng::from_os_rng()))
+            }
+            #[cfg(feature = "rand")]
+            Self::WeightedRandom { temperature } => {
+                use rand::{SeedableRng, rngs::StdRng};
+                let mut picker = PickWeightedRandom::new(StdRng::from_os_rng());
+                picker.temperature = temperature;
+                actor.insert(picker)
+            }
+        };
+    }
+}
+
+/// [`Component`] recording the [`Score`] entities spawned onto an actor entity from a [`UtilityTree`].
+#[derive(Component, Reflect)]
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+#[reflect(Component, PartialEq, Debug, Default)]
+pub struct UtilityTreeInstance {
+    /// The root [`Score`] entity of each choice, in order.
+    pub scores: Vec<Entity>,
+}
+
+/// An error that can occur when validating or spawning a [`UtilityTree`].
+#[derive(Error, Debug)]
+pub enum UtilityTreeError {
+    /// The type path isn't registered in the [`AppTypeRegistry`].
+    #[error("unknown type path `{0}`, is the type registered?")]
+    UnknownType(String),
+    /// The type isn't a [`Component`], or isn't reflected as one.
+    #[error("`{0}` is not a component, is it registered with `#[reflect(Component)]`?")]
+    NotAComponent(String),
+    /// The type doesn't reflect [`Default`].
+    #[error("`{0}` has no default value, is it registered with `#[reflect(Default)]`?")]
+    NoDefault(String),
+}
+
+/// Returns the registration of the given type path, or short type path.
+fn registration<'a>(registry: &'a TypeRegistry, type_path: &str) -> Result<&'a TypeRegistration, UtilityTreeError> {
+    registry
+        .get_with_type_path(type_path)
+        .or_else(|| registry.get_with_short_type_path(type_path))
+        .ok_or_else(|| UtilityTreeError::UnknownType(type_path.to_owned()))
+}
+
+/// Returns the [`ComponentId`] of the action with the given type path, registering the component if needed.
+fn action_id(world: &mut World, registry: &TypeRegistry, type_path: &str) -> Result<ComponentId, UtilityTreeError> {
+    let registration = registration(registry, type_path)?;
+    if let Some(id) = world.components().get_id(registration.type_id()) {
+        return Ok(id);
+    }
+    registration
+        .data::<ReflectComponent>()
+        .map(|component| component.register_component(world))
+        .ok_or_else(|| UtilityTreeError::NotAComponent(type_path.to_owned()))
+}
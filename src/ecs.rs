@@ -1,7 +1,6 @@
 //! [`bevy`] ECS utilities for implementing library functionality.
 
 use std::{
-    collections::VecDeque,
     iter::{self, FusedIterator},
     marker::PhantomData,
 };
@@ -18,6 +17,8 @@ use bevy::{
     prelude::*,
 };
 
+use crate::scoring::ScoreTree;
+
 /// A [`TriggerTargets`] used by the action [`Event`]s to trigger an action [`ComponentId`] for a given entity.
 #[derive(Reflect, Clone, Copy, PartialEq, Eq, Debug)]
 #[reflect(PartialEq, Debug)]
@@ -49,18 +50,24 @@ impl<E, B: Bundle> TriggerGetEntity for Trigger<'_, E, B> {
     }
 }
 
+/// Error returned by [`AncestorQuery`] when an ancestor could not be fetched.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum AncestorQueryError {
+    /// The underlying [`Query`] failed to fetch the ancestor entity.
     Bevy(QueryEntityError),
+    /// No ancestor with the component was found, starting from or above the given entity.
     NoSuchEntityError(Entity),
 }
 
-/// A [`Query`] wrapper that finds the closest ancestor entity with a given component.
+/// A [`Query`] wrapper that finds the closest ancestor entity with a given component,
+/// crawling up the scoring tree as configured by the [`ScoreHierarchy`](crate::scoring::ScoreHierarchy).
 /// Uses a cache to speed up subsequent queries.
 #[derive(SystemParam)]
 pub struct AncestorQuery<'w, 's, T: ReferenceType> {
-    /// The query to find the component, crawling up the hierarchy if necessary.
-    check: Query<'w, 's, (<T as ReferenceType>::Has, Option<&'static ChildOf>)>,
+    /// The query to find the component.
+    check: Query<'w, 's, <T as ReferenceType>::Has>,
+    /// The scoring tree to crawl up if necessary.
+    tree: ScoreTree<'w, 's>,
     /// The query to grab the component. This query wouldn't be necessary if rust wouldn't complain!
     fetch: Query<'w, 's, T>,
     /// Caches a given entity's closest ancestor entity with the component T.
@@ -73,17 +80,17 @@ impl<'w, T: ReferenceType> AncestorQuery<'w, '_, T> {
         // Crawl up the hierarchy
         let mut current = start;
         loop {
-            match self.check.get(current) {
-                Ok((true, _)) => {
+            match (self.check.get(current), self.tree.parent(current)) {
+                (Ok(true), _) => {
                     // Found the component, cache it and return
                     self.cache.insert(start, current);
                     return Ok(current);
                 }
-                Ok((false, Some(parent))) => {
+                (Ok(false), Some(parent)) => {
                     // Continue searching up the hierarchy
-                    current = parent.0;
+                    current = parent;
                 }
-                Ok((false, None)) | Err(_) => {
+                (Ok(false), None) | (Err(_), _) => {
                     // No parent with the component found
                     return Err(AncestorQueryError::NoSuchEntityError(current));
                 }
@@ -108,7 +115,7 @@ impl<T: Component> AncestorQuery<'_, '_, &'static T> {
         if let Entry::Occupied(entry) = self.cache.entry(start) {
             if self.fetch.contains(*entry.get()) {
                 // Cache hit
-                return self.fetch.get(*entry.get()).map_err(|e| AncestorQueryError::Bevy(e));
+                return self.fetch.get(*entry.get()).map_err(AncestorQueryError::Bevy);
             }
 
             // Cache miss
@@ -116,7 +123,7 @@ impl<T: Component> AncestorQuery<'_, '_, &'static T> {
         }
 
         let found = self.find(start)?;
-        return self.fetch.get(found).map_err(|e| AncestorQueryError::Bevy(e));
+        self.fetch.get(found).map_err(AncestorQueryError::Bevy)
     }
 }
 
@@ -126,15 +133,12 @@ impl<T: Component<Mutability = Mutable>> AncestorQuery<'_, '_, &'static mut T> {
     /// # Errors
     ///
     /// If the entity does not exist or the component is not found.
-    pub fn get_mut(&mut self, start: Entity) -> Result<Mut<T>, AncestorQueryError> {
+    pub fn get_mut(&mut self, start: Entity) -> Result<Mut<'_, T>, AncestorQueryError> {
         // Check the cache first
         if let Entry::Occupied(entry) = self.cache.entry(start) {
             if self.fetch.contains(*entry.get()) {
                 // Cache hit
-                return self
-                    .fetch
-                    .get_mut(*entry.get())
-                    .map_err(|e| AncestorQueryError::Bevy(e));
+                return self.fetch.get_mut(*entry.get()).map_err(AncestorQueryError::Bevy);
             }
 
             // Cache miss
@@ -142,7 +146,7 @@ impl<T: Component<Mutability = Mutable>> AncestorQuery<'_, '_, &'static mut T> {
         }
 
         let found = self.find(start)?;
-        self.fetch.get_mut(found).map_err(|e| AncestorQueryError::Bevy(e))
+        self.fetch.get_mut(found).map_err(AncestorQueryError::Bevy)
     }
 }
 
@@ -206,6 +210,18 @@ impl<'w, 's, R: Resource + Default> OnceCommands<'w, 's, R> {
     }
 }
 
+/// [`Resource`] marking that the `N`th [`Observer`] of the [`Component`] `T` has been spawned,
+/// used with [`CommandsExt::once`] by derived [`Scorer`](crate::scoring::Scorer)s.
+#[doc(hidden)]
+#[derive(Resource)]
+pub struct ObserverSpawned<T: 'static, const N: usize>(PhantomData<fn() -> T>);
+
+impl<T: 'static, const N: usize> Default for ObserverSpawned<T, N> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
 /// [`Commands`] extension trait for library-specific commands.
 pub trait CommandsExt {
     /// Returns a [`Commands`] wrapper that provides a way to run commands only once, based on the presence of [`Resource`] `R`.
@@ -219,12 +235,18 @@ impl CommandsExt for Commands<'_, '_> {
     }
 }
 
-/// [`SystemParam`] that provides a depth-first search post-order traversal of the entity hierarchy,
-/// starting from a given root [`Entity`].
+/// [`SystemParam`] that provides a depth-first search post-order traversal of the scoring tree,
+/// as configured by the [`ScoreHierarchy`](crate::scoring::ScoreHierarchy), starting from a given root [`Entity`].
+///
+/// Only the children of entities matching the [`QueryFilter`] `F` are traversed.
 #[derive(SystemParam)]
 pub struct DFSPostTraversal<'w, 's, F: QueryFilter + 'static = ()> {
-    children: Query<'w, 's, &'static Children, F>,
-    queue: Local<'s, VecDeque<(usize, Entity)>>,
+    /// The entities whose children are traversed.
+    traversed: Query<'w, 's, (), F>,
+    /// The scoring tree to traverse.
+    tree: ScoreTree<'w, 's>,
+    /// The entities currently being traversed, along with the index of their next child to visit.
+    stack: Local<'s, Vec<(Entity, usize)>>,
 }
 
 impl<'w, 's, F: QueryFilter + 'static> DFSPostTraversal<'w, 's, F> {
@@ -241,20 +263,14 @@ impl<'w, 's, F: QueryFilter + 'static> DFSPostTraversal<'w, 's, F> {
 /// [`Iterator`] type returned by [`DFSPostTraversal::iter`].
 pub struct DFSPostTraversalIter<'a, 'w, 's, F: QueryFilter + 'static> {
     param: &'a mut DFSPostTraversal<'w, 's, F>,
-    visited: usize,
-    current_depth: usize,
 }
 
 impl<'a, 'w, 's, F: QueryFilter + 'static> DFSPostTraversalIter<'a, 'w, 's, F> {
     fn new(param: &'a mut DFSPostTraversal<'w, 's, F>, root: Entity) -> Self {
-        param.queue.clear();
-        param.queue.push_back((0, root));
+        param.stack.clear();
+        param.stack.push((root, 0));
 
-        Self {
-            param,
-            visited: 0,
-            current_depth: 0,
-        }
+        Self { param }
     }
 }
 
@@ -262,42 +278,26 @@ impl<F: QueryFilter + 'static> Iterator for DFSPostTraversalIter<'_, '_, '_, F>
     type Item = Entity;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.param.queue.is_empty() {
-            return None;
-        }
-
-        // Exhaust all children for the first branch
+        // Exhaust all children of the current branch before visiting its parent
         loop {
-            let i = self.visited;
-            let Some(&(depth, entity)) = self.param.queue.get(i) else {
-                break;
-            };
-
-            // This node is not a child nor a sibling
-            if self.current_depth > depth {
-                break;
-            }
+            let (entity, next_child) = self.param.stack.last_mut()?;
+            let entity = *entity;
 
-            self.visited += 1;
-            self.current_depth = depth;
-
-            let Ok(entity_children) = self.param.children.get(entity) else {
-                // No children
-                break;
+            let child = if self.param.traversed.contains(entity) {
+                self.param.tree.children(entity).get(*next_child).copied()
+            } else {
+                None
             };
 
-            // TODO: can we replace this with some kind of `extend_at`?
-            for (j, child) in entity_children.into_iter().copied().enumerate() {
-                self.param.queue.insert(i + j + 1, (depth + 1, child));
+            if let Some(child) = child {
+                *next_child += 1;
+                self.param.stack.push((child, 0));
+            } else {
+                // No children left to visit
+                self.param.stack.pop();
+                return Some(entity);
             }
         }
-
-        let (depth, entity) = self.param.queue.remove(self.visited - 1)?;
-
-        self.visited -= 1;
-        self.current_depth = depth;
-
-        Some(entity)
     }
 }
 
@@ -1,9 +1,10 @@
 //! Events that define the lifecycle of the library.
 //!
-//! These events are split into three categories:
+//! These events are split into four categories:
 //! - Scoring events
 //! - Picking events
 //! - Acting events
+//! - Turn events
 //!
 //! Generally speaking, events that start with `On` should only be listened to,
 //! while other events should only be triggered.
@@ -14,6 +15,8 @@
 //! [`Score`] entities with [`Score`] children will be scored after their children, to ensure correct scoring.
 //! This will trigger the [`OnScore`] event for the target entity, which should be listened to by scoring [`Observer`]s
 //! to calculate the [`Score`] for a given entity.
+//! Right after, the [`OnScorePostProcess`] event is triggered for the same entity, which can be listened to
+//! to adjust the freshly calculated [`Score`] before its parent is scored.
 //!
 //! # Picking events
 //!
@@ -25,11 +28,24 @@
 //!
 //! [`RequestAction`] can be triggered to request an action to be initiated for a specific entity.
 //! This will trigger the [`OnActionInitiated`] event for the target entity, using the action picked by their [`Picker`].
-//! The [`OnActionEnded`] event is triggered by action lifecycle or actions themselves to indicate that they have completed or been cancelled.
+//! Actions picked from [`Targets`] score entities carry the [`Entity`] to act on along with them.
+//! The [`OnActionEnded`] event is triggered by action lifecycle or actions themselves to indicate that they have completed, failed, or been cancelled.
 //! In between these two previous events, the action should be executed.
+//! If the current action can't be [interrupted](crate::acting::ActionInterrupts) by the requested one,
+//! the [`OnActionRejected`] event is triggered instead.
+//!
+//! # Turn events
+//!
+//! [`AdvanceTurn`] can be triggered to score, pick, and initiate actions for the next actor(s) in the [`TurnQueue`],
+//! when using the [`TurnBasedLifecyclePlugin`].
 //!
 //! [`Score`]: crate::scoring::Score
 //! [`Picker`]: crate::picking::Picker
+//! [`Targets`]: crate::scoring::Targets
+//! [`TurnQueue`]: crate::TurnQueue
+//! [`TurnBasedLifecyclePlugin`]: crate::TurnBasedLifecyclePlugin
+
+use std::borrow::Cow;
 
 use bevy::{ecs::component::ComponentId, prelude::*};
 
@@ -54,6 +70,16 @@ pub struct RunScoring;
 #[reflect(Component, PartialEq, Debug, Default)]
 pub struct OnScore;
 
+/// This [`Event`] is listened to by post-processing systems to adjust the [`Score`] of a given entity,
+/// after it has been calculated by [`OnScore`] and before its parent is scored.
+/// DO NOT TRIGGER MANUALLY, trigger [`RunScoring`] instead.
+///
+/// [`Score`]: crate::scoring::Score
+#[derive(Component, Event, Reflect)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[reflect(Component, PartialEq, Debug, Default)]
+pub struct OnScorePostProcess;
+
 ////////////////////////////////////////////////////////////
 // Picking events
 ////////////////////////////////////////////////////////////
@@ -74,13 +100,33 @@ pub struct OnPick;
 /// Listen to this [`Event`] to check which action was picked for the target actor entity.
 /// This [`Event`] is triggered by [`Picker`]s to indicate that an action has been picked for the target actor entity.
 ///
+/// Created with [`OnPicked::new`] and [`OnPicked::with_target`], so that more fields can be added later.
+///
 /// [`Picker`]: crate::picking::Picker
 #[derive(Component, Event, Reflect)]
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 #[reflect(Component, PartialEq, Debug)]
+#[non_exhaustive]
 pub struct OnPicked {
     /// [`ComponentId`] of the action that was picked.
     pub action: ComponentId,
+    /// The target [`Entity`] to act on that was picked along with the action, if any.
+    pub target: Option<Entity>,
+}
+
+impl OnPicked {
+    /// Creates a new [`OnPicked`] event for the given action, without a target.
+    #[must_use]
+    pub fn new(action: ComponentId) -> Self {
+        Self { action, target: None }
+    }
+
+    /// Sets the target [`Entity`] picked along with the action.
+    #[must_use]
+    pub fn with_target(mut self, target: impl Into<Option<Entity>>) -> Self {
+        self.target = target.into();
+        self
+    }
 }
 
 ////////////////////////////////////////////////////////////
@@ -90,29 +136,104 @@ pub struct OnPicked {
 /// Trigger this [`Event`] to request a specific action or the picked action to be initiated for the target actor entity.
 ///
 /// This event SHOULD NOT be triggered without a target entity.
+/// For actors with multiple [`ActionLayer`]s, target the layer entity instead.
+///
+/// Created with [`RequestAction::pick`] or [`RequestAction::action`], and [`RequestAction::with_target`],
+/// so that more fields can be added later.
+///
+/// [`ActionLayer`]: crate::acting::ActionLayer
 #[derive(Component, Event, Reflect)]
 #[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
 #[reflect(Component, PartialEq, Debug, Default)]
+#[non_exhaustive]
 pub struct RequestAction {
     /// The [`ComponentId`] of the action that was requested, if any.
     pub action: Option<ComponentId>,
+    /// The target [`Entity`] to act on, if any.
+    /// Ignored if no action is requested, in which case the target picked along with the action is used.
+    pub target: Option<Entity>,
+}
+
+impl RequestAction {
+    /// Creates a new [`RequestAction`] event for the action picked by the actor's [`Picker`],
+    /// along with its picked target.
+    ///
+    /// [`Picker`]: crate::picking::Picker
+    #[must_use]
+    pub fn pick() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new [`RequestAction`] event for the given action, without a target.
+    #[must_use]
+    pub fn action(action: ComponentId) -> Self {
+        Self {
+            action: Some(action),
+            target: None,
+        }
+    }
+
+    /// Sets the target [`Entity`] to act on.
+    #[must_use]
+    pub fn with_target(mut self, target: impl Into<Option<Entity>>) -> Self {
+        self.target = target.into();
+        self
+    }
 }
 
 /// This [`Event`] is triggered by action lifecycle to indicate that they have been initiated.
+///
+/// Created with [`OnActionInitiated::new`] and [`OnActionInitiated::with_target`],
+/// so that more fields can be added later.
 #[derive(Component, Event, Reflect)]
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 #[reflect(Component, PartialEq, Debug)]
+#[non_exhaustive]
 pub struct OnActionInitiated {
     /// [`ComponentId`] of the action that was initiated.
     pub action: ComponentId,
+    /// The target [`Entity`] to act on, if any.
+    pub target: Option<Entity>,
+}
+
+impl OnActionInitiated {
+    /// Creates a new [`OnActionInitiated`] event for the given action, without a target.
+    #[must_use]
+    pub fn new(action: ComponentId) -> Self {
+        Self { action, target: None }
+    }
+
+    /// Sets the target [`Entity`] to act on.
+    #[must_use]
+    pub fn with_target(mut self, target: impl Into<Option<Entity>>) -> Self {
+        self.target = target.into();
+        self
+    }
+}
+
+/// This [`Event`] is triggered by action lifecycle to indicate that a [requested][`RequestAction`] action
+/// was not initiated, because the current action can't be [interrupted](crate::acting::ActionInterrupts) by it,
+/// or because an [`exclusive`](crate::acting::ActionLayer::exclusive) sibling layer is busy.
+#[derive(Component, Event, Reflect)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[reflect(Component, PartialEq, Debug)]
+pub struct OnActionRejected {
+    /// [`ComponentId`] of the action that was rejected.
+    pub action: ComponentId,
+    /// The target [`Entity`] the rejected action would have acted on, if any.
+    pub target: Option<Entity>,
+    /// [`ComponentId`] of the current action that kept running instead,
+    /// which is the action of the exclusive sibling layer if that's what blocked the request.
+    pub current: ComponentId,
 }
 
 /// This [`Event`] is triggered by action lifecycle or actions themselves to indicate
-/// that they have completed or been cancelled.
+/// that they have completed, failed, or been cancelled.
 ///
-/// An action will be cancelled if a different action is [requested][`RequestAction`] before it completes.
+/// An action will be cancelled if a different action is [requested][`RequestAction`] before it completes,
+/// and time out if it takes longer than its [timeout](crate::acting::ActionTimeouts).
 #[derive(Component, Event, Reflect)]
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[derive(Clone, PartialEq, Eq, Debug)]
 #[reflect(Component, PartialEq, Debug)]
 pub struct OnActionEnded {
     /// [`ComponentId`] of the action that was finished.
@@ -139,15 +260,59 @@ impl OnActionEnded {
             reason: ActionEndReason::Cancelled,
         }
     }
+
+    /// Creates a new [`TimedOut`][`ActionEndReason::TimedOut`] [`OnActionEnded`] event with the given action.
+    #[must_use]
+    pub fn timed_out(action: ComponentId) -> Self {
+        Self {
+            action,
+            reason: ActionEndReason::TimedOut,
+        }
+    }
+
+    /// Creates a new [`Failed`][`ActionEndReason::Failed`] [`OnActionEnded`] event with the given action and reason.
+    ///
+    /// The reason can be a static description, or one built at runtime, like which target had no path.
+    #[must_use]
+    pub fn failed(action: ComponentId, reason: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            action,
+            reason: ActionEndReason::Failed(Some(reason.into())),
+        }
+    }
 }
 
 /// The reason [`OnActionEnded`] was triggered.
 #[derive(Reflect)]
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[derive(Clone, PartialEq, Eq, Debug)]
 #[reflect(PartialEq, Debug)]
 pub enum ActionEndReason {
     /// The action was completed successfully.
     Completed,
     /// The action was cancelled.
     Cancelled,
+    /// The action took longer than its timeout, see [`ActionTimeouts`](crate::acting::ActionTimeouts).
+    TimedOut,
+    /// The action could not be completed, e.g. because no path to its target was found,
+    /// optionally with a description of why.
+    ///
+    /// What happens next is configured per action in [`ActionFailurePolicies`](crate::acting::ActionFailurePolicies).
+    Failed(Option<Cow<'static, str>>),
 }
+
+////////////////////////////////////////////////////////////
+// Turn events
+////////////////////////////////////////////////////////////
+
+/// Trigger this [`Event`] without a target to start the next turn of the [`TurnQueue`].
+///
+/// The actor(s) whose turn it is are scored, pick an action, and have it initiated.
+/// Ignored while the actions of the previous turn haven't ended yet.
+/// See [`TurnBasedLifecyclePlugin`] for more information.
+///
+/// [`TurnQueue`]: crate::TurnQueue
+/// [`TurnBasedLifecyclePlugin`]: crate::TurnBasedLifecyclePlugin
+#[derive(Component, Event, Reflect)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[reflect(Component, PartialEq, Debug, Default)]
+pub struct AdvanceTurn;
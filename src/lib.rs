@@ -28,6 +28,8 @@
 //! In this crate, actor entities hold the view into the world,
 //! and child [`Score`] entities hold and calculate the scores based on that view.
 //! These children can be nested to any depth, giving you the flexibility to score entities however complex you want.
+//! Scoring trees are built with either [`ChildOf`](bevy::prelude::ChildOf)
+//! or the dedicated [`ScoreOf`](crate::scoring::ScoreOf) relationship, see [`ScoreHierarchy`](crate::scoring::ScoreHierarchy).
 //!
 //! # Full Walkthrough Example
 //!
@@ -205,25 +207,38 @@
 //! - Run scoring and picking systems at a slower fixed rate than default.
 //!     - This will induce latency in the AI, but will reduce overall frame time.
 //! - Replace deeply nested scoring hierarchies with shallow hand-written scoring observers.
+//! - Score independent actors in parallel with [`ScoringPlugin::parallel`](crate::scoring::ScoringPlugin::parallel)
+//!   by marking their scoring trees with [`SkipScoringEvents`](crate::scoring::SkipScoringEvents).
+//! - Spread actors across several ticks with [`RealtimeLifecyclePlugin::scheduling`].
 //!
 //! [`Score`]: crate::scoring::Score
 
 #![warn(missing_docs)]
 
+use std::{collections::VecDeque, time::Duration};
+
 use bevy::{
     app::PluginGroupBuilder,
-    ecs::schedule::{InternedScheduleLabel, ScheduleLabel},
+    ecs::{
+        entity::EntityHashSet,
+        schedule::{InternedScheduleLabel, ScheduleLabel},
+    },
+    platform::time::Instant,
     prelude::*,
 };
 
 use crate::{
-    acting::{ActionPlugin, CurrentAction},
-    event::{RequestAction, RunPicking, RunScoring},
+    acting::{ActionPlugin, CurrentAction, CurrentTarget},
+    event::{AdvanceTurn, OnActionEnded, OnActionInitiated, RequestAction, RunPicking, RunScoring},
     picking::{Picker, PickingPlugin},
-    scoring::ScoringPlugin,
+    scoring::{Score, ScoreHierarchy, ScoringPlugin},
 };
 
+extern crate self as bevy_observed_utility;
+
 pub mod acting;
+#[cfg(feature = "asset")]
+pub mod asset;
 pub mod ecs;
 pub mod event;
 pub mod picking;
@@ -232,23 +247,49 @@ pub mod scoring;
 pub mod prelude {
     //! Re-exports important traits and types.
     pub use crate::{
-        ObservedUtilityPlugins,
+        LifecycleScheduling, ObservedUtilityPlugins, ThinkRate, TurnBasedLifecyclePlugin, TurnMode, TurnQueue,
         acting::{
-            CurrentAction, on_action_ended_remove, on_action_initiated_insert_default,
-            on_action_initiated_insert_from_resource,
+            ActionFailurePolicies, ActionInterrupts, ActionLayer, ActionSequences, ActionTimeout, ActionTimeouts,
+            CurrentAction, CurrentTarget, FailurePolicy, Interruptibility, Sequence, on_action_ended_remove,
+            on_action_initiated_insert_default, on_action_initiated_insert_from_resource,
         },
         ecs::{AncestorQuery, TargetedAction},
-        event::{ActionEndReason, OnActionEnded, OnActionInitiated, OnPick, OnPicked, OnScore, RunPicking, RunScoring},
-        picking::{FirstToScore, Highest, Picker},
+        event::{
+            ActionEndReason, AdvanceTurn, OnActionEnded, OnActionInitiated, OnActionRejected, OnPick, OnPicked,
+            OnScore, OnScorePostProcess, RunPicking, RunScoring,
+        },
+        picking::{FirstToScore, Highest, Momentum, Picker},
         scoring::{
-            AllOrNothing, Evaluated, Evaluator, FixedScore, LinearEvaluator, Measure, Measured, PowerEvaluator,
-            Product, Score, SigmoidEvaluator, Sum, Weighted, WeightedMax, WeightedProduct, WeightedRMS, WeightedSum,
-            Winning, score_ancestor,
+            AllOrNothing, ChildAggregateScorer, Cooldown, CooldownLength, Evaluated, Evaluator, FixedScore,
+            LinearEvaluator, Measure, Measured, PostEvaluated, PowerEvaluator, Product, Score, ScoreClock,
+            ScoreHierarchy, ScoreHistory, ScoreOf, ScoreTimestamp, Scorer, Scorers, SigmoidEvaluator, Sum, Targets,
+            Volatile, Weighted, WeightedMax, WeightedProduct, WeightedRMS, WeightedSum, Winning, score_ancestor,
+            score_target,
         },
     };
 
+    #[cfg(feature = "asset")]
+    pub use crate::asset::{UtilityTree, UtilityTreeHandle, UtilityTreeInstance, UtilityTreePlugin};
+
+    #[cfg(feature = "trace")]
+    pub use crate::scoring::ScoringTrace;
+
     #[cfg(feature = "rand")]
-    pub use crate::{picking::PickRandom, scoring::RandomScore};
+    pub use crate::{
+        picking::{PickCutoff, PickRandom, PickWeightedRandom},
+        scoring::RandomScore,
+    };
+}
+
+/// Items used by the code generated by this crate's derive macros.
+#[doc(hidden)]
+pub mod __private {
+    pub use bevy;
+
+    pub use crate::{
+        ecs::{CommandsExt, ObserverSpawned},
+        scoring::{ChildAggregateScorer, ParallelScorer, Score, Weighted, score_children_aggregate},
+    };
 }
 
 /// [`PluginGroup`] for all standard plugins in `bevy_observed_utility`.
@@ -260,10 +301,10 @@ pub enum ObservedUtilityPlugins {
     RealTime,
     /// Config meant for turn-based games.
     ///
-    /// This does NOT include the [`RealtimeLifecyclePlugin`],
-    /// so you'll need to run scoring, picking, and action selection manually.
+    /// This does NOT include the [`RealtimeLifecyclePlugin`], but includes the [`TurnBasedLifecyclePlugin`],
+    /// so you'll need to trigger [`AdvanceTurn`] to run scoring, picking, and action selection turn by turn.
     ///
-    /// To do so, trigger the [`RunScoring`] and [`RunPicking`] events un-targeted,
+    /// Alternatively, trigger the [`RunScoring`] and [`RunPicking`] events un-targeted,
     /// which will score and pick actions for all entities with the appropriate components.
     /// Then trigger the [`RequestAction`] event targeted at an actor entity when you want them to perform an action.
     TurnBased,
@@ -272,12 +313,12 @@ pub enum ObservedUtilityPlugins {
 impl PluginGroup for ObservedUtilityPlugins {
     fn build(self) -> PluginGroupBuilder {
         let builder = PluginGroupBuilder::start::<Self>()
-            .add(ScoringPlugin)
+            .add(ScoringPlugin::default())
             .add(PickingPlugin)
             .add(ActionPlugin);
         match self {
             ObservedUtilityPlugins::RealTime => builder.add(RealtimeLifecyclePlugin::default()),
-            ObservedUtilityPlugins::TurnBased => builder,
+            ObservedUtilityPlugins::TurnBased => builder.add(TurnBasedLifecyclePlugin::default()),
         }
     }
 }
@@ -289,14 +330,32 @@ impl PluginGroup for ObservedUtilityPlugins {
 pub struct RealtimeLifecyclePlugin {
     /// The [`ScheduleLabel`] to run scoring and picking, and action selection in.                                                      
     pub score_pick_perform_in: InternedScheduleLabel,
+    /// Which actors are scored and picked for each run of the schedule, see [`LifecycleScheduling`].
+    ///
+    /// This is inserted as a [`Resource`], so it can be changed at runtime.
+    ///
+    /// Defaults to [`LifecycleScheduling::EveryTick`].
+    pub scheduling: LifecycleScheduling,
 }
 
 impl Plugin for RealtimeLifecyclePlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(
-            self.score_pick_perform_in,
-            (Self::score_and_pick, Self::request_action_if_none_or_default),
-        );
+        app.insert_resource(self.scheduling)
+            .register_type::<LifecycleScheduling>()
+            .register_type::<ThinkRate>()
+            .add_systems(
+                self.score_pick_perform_in,
+                (
+                    Self::score_and_pick.run_if(
+                        resource_equals(LifecycleScheduling::EveryTick).and(not(any_with_component::<ThinkRate>)),
+                    ),
+                    Self::score_and_pick_staggered.run_if(
+                        not(resource_equals(LifecycleScheduling::EveryTick)).or(any_with_component::<ThinkRate>),
+                    ),
+                    Self::request_action_if_none_or_default,
+                )
+                    .chain(),
+            );
     }
 }
 
@@ -304,6 +363,7 @@ impl Default for RealtimeLifecyclePlugin {
     fn default() -> Self {
         Self {
             score_pick_perform_in: FixedPostUpdate.intern(),
+            scheduling: LifecycleScheduling::default(),
         }
     }
 }
@@ -315,6 +375,124 @@ impl RealtimeLifecyclePlugin {
         commands.trigger(RunPicking);
     }
 
+    /// [`System`] that runs scoring and picking [`Observer`]s for the actors that are due this tick,
+    /// according to the [`LifecycleScheduling`] resource and their [`ThinkRate`]s.
+    ///
+    /// Each due actor's scoring trees are scored with a targeted [`RunScoring`],
+    /// followed by a targeted [`RunPicking`] for the actor.
+    /// Actors that aren't due keep their previous [`Score`]s and picked action.
+    ///
+    /// With [`LifecycleScheduling::EveryTick`], scoring trees without a rate-limited actor, including those
+    /// without any actor, are still scored on every tick.
+    pub fn score_and_pick_staggered(
+        world: &mut World,
+        actors: &mut QueryState<Entity, With<Picker>>,
+        mut tick: Local<usize>,
+        mut pending: Local<VecDeque<Entity>>,
+        mut roster: Local<Vec<Entity>>,
+    ) {
+        let scheduling = world.get_resource::<LifecycleScheduling>().copied().unwrap_or_default();
+        let now = world.get_resource::<Time>().map(Time::elapsed);
+        match scheduling {
+            LifecycleScheduling::EveryTick => {
+                let all: Vec<Entity> = actors.iter(world).collect();
+                let waiting: EntityHashSet = all
+                    .into_iter()
+                    .filter(|&actor| !Self::think(world, actor, now))
+                    .collect();
+                if waiting.is_empty() {
+                    world.trigger(RunScoring);
+                    world.flush();
+                    world.trigger(RunPicking);
+                    world.flush();
+                    return;
+                }
+
+                // Only leave out the actors that aren't due, so actor-less scoring trees are still scored
+                let hierarchy = ScoreHierarchy::of(world);
+                let roots: Vec<Entity> = world
+                    .query_filtered::<EntityRef, With<Score>>()
+                    .iter(world)
+                    .filter(|entity| {
+                        hierarchy
+                            .parent(entity)
+                            .is_none_or(|parent| !waiting.contains(&parent) && world.get::<Score>(parent).is_none())
+                    })
+                    .map(|entity| entity.id())
+                    .collect();
+                for root in roots {
+                    world.trigger_targets(RunScoring, root);
+                    world.flush();
+                }
+                let due: Vec<Entity> = actors.iter(world).filter(|actor| !waiting.contains(actor)).collect();
+                for actor in due {
+                    world.trigger_targets(RunPicking, actor);
+                    world.flush();
+                }
+            }
+            LifecycleScheduling::RoundRobin { buckets } => {
+                let buckets = buckets.max(1) as usize;
+                let bucket = *tick % buckets;
+                *tick += 1;
+
+                // Keep actors in the order they were first seen, so the buckets stay balanced
+                let current: EntityHashSet = actors.iter(world).collect();
+                roster.retain(|actor| current.contains(actor));
+                let known: EntityHashSet = roster.iter().copied().collect();
+                roster.extend(actors.iter(world).filter(|actor| !known.contains(actor)));
+
+                let due: Vec<Entity> = roster.iter().skip(bucket).step_by(buckets).copied().collect();
+                for actor in due {
+                    if Self::think(world, actor, now) {
+                        score_and_pick_actor(world, actor);
+                    }
+                }
+            }
+            LifecycleScheduling::TimeBudget(budget) => {
+                let start = Instant::now();
+                if pending.is_empty() {
+                    // Start the next round with all current actors
+                    pending.extend(actors.iter(world));
+                }
+
+                // At least one actor is processed per tick, so every actor is eventually reached
+                while let Some(actor) = pending.pop_front() {
+                    if world.get_entity(actor).is_err() {
+                        // Despawned since the round started
+                        continue;
+                    }
+                    if !Self::think(world, actor, now) {
+                        // Not due yet, which doesn't use up the budget
+                        continue;
+                    }
+                    score_and_pick_actor(world, actor);
+                    if start.elapsed() >= budget {
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns whether the given actor entity is due to think according to its [`ThinkRate`], if any,
+    /// recording that it thinks now if so.
+    fn think(world: &mut World, actor: Entity, now: Option<Duration>) -> bool {
+        let Some(now) = now else {
+            // Without time, think rates can't be followed
+            return true;
+        };
+        match world.get::<ThinkRate>(actor) {
+            Some(rate) if !rate.is_due(now) => false,
+            Some(_) => {
+                if let Some(mut rate) = world.get_mut::<ThinkRate>(actor) {
+                    rate.last_thought = Some(now);
+                }
+                true
+            }
+            None => true,
+        }
+    }
+
     /// [`System`] that requests a new action for an actor if they're currently "idling",
     /// i.e. performing their default action.
     pub fn request_action_if_none_or_default(
@@ -323,8 +501,510 @@ impl RealtimeLifecyclePlugin {
     ) {
         for (actor, picker, current_action) in actors.iter() {
             if current_action.is_some_and(|ca| picker.is_default(ca.0)) || current_action.is_none() {
-                commands.trigger_targets(RequestAction { action: None }, actor);
+                commands.trigger_targets(RequestAction::pick(), actor);
+            }
+        }
+    }
+}
+
+/// Scores the scoring trees of the given actor entity, then picks for it.
+pub(crate) fn score_and_pick_actor(world: &mut World, actor: Entity) {
+    let hierarchy = ScoreHierarchy::of(world);
+    let roots: Vec<Entity> = world
+        .get_entity(actor)
+        .map(|actor| hierarchy.children(&actor).to_vec())
+        .unwrap_or_default();
+
+    for root in roots {
+        if world.get::<Score>(root).is_some() {
+            world.trigger_targets(RunScoring, root);
+            world.flush();
+        }
+    }
+    world.trigger_targets(RunPicking, actor);
+    world.flush();
+}
+
+/// [`Resource`] configuring which actors the [`RealtimeLifecyclePlugin`] scores and picks for on each tick,
+/// set with [`RealtimeLifecyclePlugin::scheduling`].
+///
+/// With many actors, scoring and picking all of them every tick can take too long.
+/// The staggered modes spread actors across ticks instead, scoring and picking for each due actor individually.
+/// Actors that aren't due keep their previous decision, so they react a few ticks later.
+/// Only the scoring trees of actor entities, i.e. entities with a [`Picker`], are scored in the staggered modes.
+///
+/// # Example
+///
+/// ```rust
+/// use std::time::Duration;
+///
+/// use bevy::prelude::*;
+/// use bevy_observed_utility::prelude::*;
+///
+/// #[derive(Component)]
+/// pub struct Drink;
+/// #[derive(Component)]
+/// pub struct Idle;
+///
+/// let mut app = App::new();
+/// app.add_plugins(ObservedUtilityPlugins::RealTime);
+/// app.insert_resource(LifecycleScheduling::RoundRobin { buckets: 2 });
+///
+/// let world = app.world_mut();
+/// let drink = world.register_component::<Drink>();
+/// let idle = world.register_component::<Idle>();
+///
+/// let mut actors = Vec::new();
+/// let mut thirsts = Vec::new();
+/// for _ in 0..4 {
+///     let actor = world.spawn_empty().id();
+///     let thirst = world.spawn((FixedScore::new(0.8), Score::default(), ScoreOf(actor))).id();
+///     world.entity_mut(actor).insert((Picker::new(idle).with(thirst, drink), Highest));
+///     actors.push(actor);
+///     thirsts.push(thirst);
+/// }
+///
+/// let drinking = |world: &World| {
+///     actors
+///         .iter()
+///         .filter(|&&actor| world.get::<Picker>(actor).unwrap().picked == drink)
+///         .count()
+/// };
+///
+/// // Half of the actors are scored and picked for on each tick
+/// app.world_mut().run_schedule(FixedPostUpdate);
+/// assert_eq!(2, drinking(app.world()));
+/// app.world_mut().run_schedule(FixedPostUpdate);
+/// assert_eq!(4, drinking(app.world()));
+///
+/// // With no time budget to spare, a single actor is scored and picked for on each tick
+/// app.insert_resource(LifecycleScheduling::TimeBudget(Duration::ZERO));
+/// for &thirst in &thirsts {
+///     app.world_mut().get_mut::<Score>(thirst).unwrap().set(0.);
+/// }
+/// app.world_mut().run_schedule(FixedPostUpdate);
+/// let scored = thirsts.iter().filter(|&&thirst| app.world().get::<Score>(thirst).unwrap().get() > 0.);
+/// assert_eq!(1, scored.count());
+/// ```
+#[derive(Resource, Reflect)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[reflect(Resource, PartialEq, Debug, Default)]
+pub enum LifecycleScheduling {
+    /// Scores and picks for all actors on every tick, with un-targeted [`RunScoring`] and [`RunPicking`] events.
+    ///
+    /// If any actor has a [`ThinkRate`] and isn't due, the other scoring trees are scored individually instead.
+    #[default]
+    EveryTick,
+    /// Splits actors evenly into round-robin buckets, scoring and picking for one bucket per tick.
+    ///
+    /// Actors are assigned to buckets in the order they are first seen, so new actors join the next buckets in turn.
+    ///
+    /// Every actor is scored and picked for once every `buckets` ticks.
+    RoundRobin {
+        /// The number of ticks it takes to go through all actors.
+        buckets: u32,
+    },
+    /// Scores and picks for actors one at a time until the given time budget is used up,
+    /// continuing where it left off on the next tick.
+    ///
+    /// At least one actor is scored and picked for per tick, so the budget may be overrun by a single actor.
+    TimeBudget(Duration),
+}
+
+/// [`Component`] for actor entities that limits how often the [`RealtimeLifecyclePlugin`] scores and picks for them,
+/// e.g. to let faraway actors think less often than nearby ones as a level of detail.
+///
+/// The actor thinks again once the interval has passed since it last thought, as measured by the elapsed [`Time`],
+/// which is [`Time<Fixed>`] in the default [`FixedPostUpdate`] schedule.
+/// In between, it keeps its previous [`Score`]s and picked action.
+/// The interval can be changed at any time, e.g. from the distance to the camera.
+///
+/// Think rates are combined with the [`LifecycleScheduling`], so actors think when they are both scheduled and due.
+///
+/// # Example
+///
+/// ```rust
+/// use std::time::Duration;
+///
+/// use bevy::prelude::*;
+/// use bevy_observed_utility::prelude::*;
+///
+/// #[derive(Component)]
+/// pub struct Idle;
+///
+/// let mut app = App::new();
+/// app.add_plugins(ObservedUtilityPlugins::RealTime).init_resource::<Time>();
+///
+/// let world = app.world_mut();
+/// let idle = world.register_component::<Idle>();
+///
+/// let near = world.spawn((Picker::new(idle), Highest)).id();
+/// let near_score = world.spawn((FixedScore::new(0.5), Score::default(), ScoreOf(near))).id();
+/// let far = world.spawn((Picker::new(idle), Highest, ThinkRate::new(Duration::from_secs(2)))).id();
+/// let far_score = world.spawn((FixedScore::new(0.5), Score::default(), ScoreOf(far))).id();
+/// // Scoring trees without an actor are scored on every tick too
+/// let mood = world.spawn((FixedScore::new(0.5), Score::default())).id();
+///
+/// let mut tick = |app: &mut App| {
+///     for score in [near_score, far_score, mood] {
+///         app.world_mut().get_mut::<Score>(score).unwrap().set(0.);
+///     }
+///     app.world_mut().run_schedule(FixedPostUpdate);
+///     app.world_mut().resource_mut::<Time>().advance_by(Duration::from_secs(1));
+///     let scored = |score| app.world().get::<Score>(score).unwrap().get() > 0.;
+///     (scored(near_score), scored(far_score), scored(mood))
+/// };
+///
+/// assert_eq!((true, true, true), tick(&mut app));
+/// assert_eq!((true, false, true), tick(&mut app));
+/// assert_eq!((true, true, true), tick(&mut app));
+/// ```
+#[derive(Component, Reflect)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[reflect(Component, PartialEq, Debug, Default)]
+pub struct ThinkRate {
+    /// The minimum time between thinking.
+    interval: Duration,
+    /// The elapsed time when the actor last thought, if it has yet.
+    last_thought: Option<Duration>,
+}
+
+impl ThinkRate {
+    /// Creates a new [`ThinkRate`] thinking at most once per `interval`.
+    #[must_use]
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            last_thought: None,
+        }
+    }
+
+    /// Creates a new [`ThinkRate`] thinking on every tick.
+    #[must_use]
+    pub fn every_tick() -> Self {
+        Self::new(Duration::ZERO)
+    }
+
+    /// Returns the minimum time between thinking.
+    #[must_use]
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Sets the minimum time between thinking, which takes effect from the last time the actor thought.
+    pub fn set_interval(&mut self, interval: Duration) {
+        self.interval = interval;
+    }
+
+    /// Returns the elapsed time when the actor last thought, or `None` if it hasn't yet.
+    #[must_use]
+    pub fn last_thought(&self) -> Option<Duration> {
+        self.last_thought
+    }
+
+    /// Returns whether the actor is due to think at the given elapsed time.
+    #[must_use]
+    pub fn is_due(&self, now: Duration) -> bool {
+        self.last_thought
+            .is_none_or(|last| now.saturating_sub(last) >= self.interval)
+    }
+}
+
+/// [`Plugin`] which drives scoring, picking, and acting turn by turn, following the [`TurnQueue`] [`Resource`].
+/// This plugin is included in [`ObservedUtilityPlugins::TurnBased`].
+///
+/// Each time [`AdvanceTurn`] is triggered, the actor(s) whose turn it is are scored, pick an action,
+/// and have it initiated with [`RequestAction`], even if it's the same action as in their previous turn.
+/// Further [`AdvanceTurn`] events are ignored until all of their actions have ended with [`OnActionEnded`],
+/// so every action, including default actions like idling, needs to end for the next turn to start.
+/// Actors whose request doesn't initiate any action, e.g. without a [`Picker`], are skipped.
+///
+/// Actors in the [`TurnQueue`] only act during their turns, so when their actions time out or fail,
+/// the [`ActionPlugin`] defers requesting a new one, such as a [`FailurePolicy::Retry`], to their next turn.
+///
+/// [`FailurePolicy::Retry`]: crate::acting::FailurePolicy::Retry
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_observed_utility::prelude::*;
+///
+/// #[derive(Component)]
+/// pub struct Attack;
+/// #[derive(Component)]
+/// pub struct Idle;
+///
+/// let mut app = App::new();
+/// app.add_plugins(ObservedUtilityPlugins::TurnBased);
+///
+/// let world = app.world_mut();
+/// let attack = world.register_component::<Attack>();
+/// let idle = world.register_component::<Idle>();
+///
+/// let mut spawn_actor = |world: &mut World| {
+///     let actor = world.spawn(Highest).id();
+///     let score = world.spawn((FixedScore::new(0.8), Score::default(), ScoreOf(actor))).id();
+///     world.entity_mut(actor).insert(Picker::new(idle).with(score, attack));
+///     world.resource_mut::<TurnQueue>().push(actor);
+///     actor
+/// };
+/// let first = spawn_actor(world);
+/// let second = spawn_actor(world);
+///
+/// world.trigger(AdvanceTurn);
+/// world.flush();
+/// assert_eq!(&[first], world.resource::<TurnQueue>().acting());
+///
+/// // Still waiting for the first actor's attack to end
+/// world.trigger(AdvanceTurn);
+/// world.flush();
+/// assert_eq!(&[first], world.resource::<TurnQueue>().acting());
+///
+/// world.trigger_targets(OnActionEnded::completed(attack), TargetedAction(first, attack));
+/// world.flush();
+/// world.trigger(AdvanceTurn);
+/// world.flush();
+/// assert_eq!(&[second], world.resource::<TurnQueue>().acting());
+/// ```
+#[derive(Default)]
+pub struct TurnBasedLifecyclePlugin {
+    /// Whether actors take their turns one at a time or all at once.
+    ///
+    /// This is the initial [`TurnQueue::mode`], which can be changed at runtime.
+    ///
+    /// Defaults to [`TurnMode::Sequential`].
+    pub mode: TurnMode,
+}
+
+impl Plugin for TurnBasedLifecyclePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(TurnQueue::new(self.mode))
+            .add_observer(Self::on_advance_turn)
+            .add_observer(Self::on_action_initiated)
+            .add_observer(Self::on_action_ended);
+
+        app.register_type::<TurnQueue>()
+            .register_type::<TurnMode>()
+            .register_type::<AdvanceTurn>();
+    }
+}
+
+impl TurnBasedLifecyclePlugin {
+    /// [`Observer`] that starts the next turn of the [`TurnQueue`] when [`AdvanceTurn`] is triggered.
+    pub fn on_advance_turn(_trigger: Trigger<AdvanceTurn>, mut commands: Commands) {
+        commands.queue(Self::advance);
+    }
+
+    /// [`Observer`] that marks an actor as acting when an action is initiated at the start of its turn.
+    pub fn on_action_initiated(trigger: Trigger<OnActionInitiated>, queue: Option<ResMut<TurnQueue>>) {
+        let actor = trigger.target();
+        let Some(mut queue) = queue else {
+            return;
+        };
+        if queue.starting.contains(&actor) && !queue.acting.contains(&actor) {
+            queue.acting.push(actor);
+        }
+    }
+
+    /// [`Observer`] that marks an actor as done with its turn when its [`CurrentAction`] ends.
+    ///
+    /// Other actions ending, such as the steps of a [`Sequence`](crate::acting::Sequence), are ignored.
+    pub fn on_action_ended(
+        trigger: Trigger<OnActionEnded>,
+        current_actions: Query<&CurrentAction>,
+        queue: Option<ResMut<TurnQueue>>,
+    ) {
+        let actor = trigger.target();
+        if current_actions
+            .get(actor)
+            .is_ok_and(|current_action| current_action.0 != trigger.event().action)
+        {
+            return;
+        }
+        if let Some(mut queue) = queue {
+            queue.acting.retain(|&acting| acting != actor);
+        }
+    }
+
+    /// Starts the next turn of the [`TurnQueue`], unless the actions of the previous turn haven't ended yet.
+    pub fn advance(world: &mut World) {
+        let Some(queue) = world.get_resource::<TurnQueue>() else {
+            return;
+        };
+        let despawned: Vec<Entity> = queue
+            .actors
+            .iter()
+            .chain(&queue.acting)
+            .copied()
+            .filter(|&actor| world.get_entity(actor).is_err())
+            .collect();
+
+        let mut queue = world.resource_mut::<TurnQueue>();
+        for actor in despawned {
+            queue.remove(actor);
+            // Actors may have been removed from the turn order while acting
+            queue.acting.retain(|&acting| acting != actor);
+        }
+        if !queue.acting.is_empty() {
+            return;
+        }
+        let actors = match queue.mode {
+            TurnMode::Sequential => {
+                let Some(actor) = queue.next_actor() else {
+                    return;
+                };
+                queue.next = (queue.next + 1) % queue.actors.len();
+                vec![actor]
             }
+            TurnMode::Simultaneous => queue.actors.clone(),
+        };
+        // Actors are only acting once their action is initiated, which may not happen
+        queue.starting.clone_from(&actors);
+        queue.turn += 1;
+
+        // All actors decide before any of them act
+        for &actor in &actors {
+            score_and_pick_actor(world, actor);
+        }
+        for actor in actors {
+            let Ok(mut actor_mut) = world.get_entity_mut(actor) else {
+                // Despawned while an earlier actor was scored or acting
+                world.resource_mut::<TurnQueue>().remove(actor);
+                continue;
+            };
+            // The previous turn's action has ended, so the picked action is initiated even if it's the same
+            actor_mut.remove::<(CurrentAction, CurrentTarget)>();
+            let request = world.resource_mut::<TurnQueue>().take_deferred(actor);
+            world.trigger_targets(request.unwrap_or_else(RequestAction::pick), actor);
+            world.flush();
+        }
+        world.resource_mut::<TurnQueue>().starting.clear();
+    }
+}
+
+/// Whether actors in the [`TurnQueue`] take their turns one at a time or all at once.
+#[derive(Reflect)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[reflect(PartialEq, Debug, Default)]
+pub enum TurnMode {
+    /// Each [`AdvanceTurn`] starts the turn of the next actor in order, like an initiative order.
+    #[default]
+    Sequential,
+    /// Each [`AdvanceTurn`] starts the turn of all actors at once, which all decide before any of them act.
+    Simultaneous,
+}
+
+/// [`Resource`] holding the actors taking turns with the [`TurnBasedLifecyclePlugin`], in order.
+///
+/// Actors need to be added to the queue to take turns. Despawned actors are removed automatically.
+#[derive(Resource, Reflect)]
+#[derive(Clone, PartialEq, Debug, Default)]
+#[reflect(Resource, PartialEq, Debug, Default)]
+pub struct TurnQueue {
+    /// Whether actors take their turns one at a time or all at once.
+    pub mode: TurnMode,
+    /// The actors taking turns, in order.
+    actors: Vec<Entity>,
+    /// The index of the actor whose turn is next, in [`TurnMode::Sequential`].
+    next: usize,
+    /// The actors whose actions haven't ended yet this turn.
+    acting: Vec<Entity>,
+    /// The actors whose turn is starting, while their actions are requested.
+    starting: Vec<Entity>,
+    /// The requests deferred to the next turn of their actors.
+    deferred: Vec<(Entity, RequestAction)>,
+    /// The number of turns started so far.
+    turn: u64,
+}
+
+impl TurnQueue {
+    /// Creates a new empty [`TurnQueue`] with the given [`TurnMode`].
+    #[must_use]
+    pub fn new(mode: TurnMode) -> Self {
+        Self {
+            mode,
+            ..Default::default()
+        }
+    }
+
+    /// Returns the actors taking turns, in order.
+    #[must_use]
+    pub fn actors(&self) -> &[Entity] {
+        &self.actors
+    }
+
+    /// Adds the actor to the end of the turn order, if it isn't in the queue yet.
+    pub fn push(&mut self, actor: Entity) {
+        if !self.actors.contains(&actor) {
+            self.actors.push(actor);
+        }
+    }
+
+    /// Inserts the actor into the turn order at the given index, if it isn't in the queue yet.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is greater than the number of actors.
+    pub fn insert(&mut self, index: usize, actor: Entity) {
+        if self.actors.contains(&actor) {
+            return;
+        }
+        self.actors.insert(index, actor);
+        if index < self.next {
+            self.next += 1;
+        }
+    }
+
+    /// Removes the actor from the turn order, keeping whose turn is next.
+    pub fn remove(&mut self, actor: Entity) {
+        let Some(index) = self.actors.iter().position(|&other| other == actor) else {
+            return;
+        };
+        self.actors.remove(index);
+        self.acting.retain(|&acting| acting != actor);
+        self.deferred.retain(|&(deferred, _)| deferred != actor);
+        if index < self.next {
+            self.next -= 1;
+        }
+        if self.next >= self.actors.len() {
+            self.next = 0;
         }
     }
+
+    /// Returns `true` if the actor is in the turn order.
+    #[must_use]
+    pub fn contains(&self, actor: Entity) -> bool {
+        self.actors.contains(&actor)
+    }
+
+    /// Defers the request to the next turn of the actor, replacing any request deferred before.
+    pub fn defer(&mut self, actor: Entity, request: RequestAction) {
+        self.deferred.retain(|&(deferred, _)| deferred != actor);
+        self.deferred.push((actor, request));
+    }
+
+    /// Removes and returns the request deferred to the next turn of the actor, if any.
+    pub fn take_deferred(&mut self, actor: Entity) -> Option<RequestAction> {
+        let index = self.deferred.iter().position(|&(deferred, _)| deferred == actor)?;
+        Some(self.deferred.remove(index).1)
+    }
+
+    /// Returns the actor whose turn is next in [`TurnMode::Sequential`], if there are any actors.
+    #[must_use]
+    pub fn next_actor(&self) -> Option<Entity> {
+        self.actors.get(self.next).copied()
+    }
+
+    /// Returns the actors whose actions haven't ended yet this turn.
+    #[must_use]
+    pub fn acting(&self) -> &[Entity] {
+        &self.acting
+    }
+
+    /// Returns the number of turns started so far.
+    #[must_use]
+    pub fn turn(&self) -> u64 {
+        self.turn
+    }
 }
@@ -4,7 +4,12 @@
 //!
 //! - [`FirstToScore`]: Picks the first action to reach a certain score.
 //! - [`Highest`]: Picks the action with the highest score.
-//! - [`Random`] (requires `rand` feature): Picks a random action.
+//! - [`PickRandom`] (requires `rand` feature): Picks a random action.
+//! - [`PickWeightedRandom`] (requires `rand` feature): Picks a random action, weighted by score.
+//!
+//! All of these trigger [`OnPicked`] exactly once per pick, using [`Picker::pick_and_trigger`].
+//!
+//! Add [`Momentum`] to a [`Picker`] entity to favor its currently picked action with the score-based pickers.
 //!
 //! [`Score`]: crate::scoring::Score
 
@@ -15,13 +20,19 @@ use bevy::{
 
 mod first_to_score;
 mod highest;
+mod momentum;
 #[cfg(feature = "rand")]
 mod random;
+#[cfg(feature = "rand")]
+mod weighted_random;
 
 pub use first_to_score::*;
 pub use highest::*;
+pub use momentum::*;
 #[cfg(feature = "rand")]
 pub use random::*;
+#[cfg(feature = "rand")]
+pub use weighted_random::*;
 
 use crate::{
     ecs::TriggerGetEntity,
@@ -38,10 +49,11 @@ impl Plugin for PickingPlugin {
 
         app.register_type::<Picker>()
             .register_type::<FirstToScore>()
-            .register_type::<Highest>();
+            .register_type::<Highest>()
+            .register_type::<Momentum>();
 
         #[cfg(feature = "rand")]
-        app.register_type::<Random>();
+        app.register_type::<PickCutoff>();
 
         app.register_type::<RunPicking>()
             .register_type::<OnPick>()
@@ -77,6 +89,12 @@ pub struct Picker {
     pub choices: EntityHashMap<ComponentId>,
     /// The last action [`ComponentId`] picked by the picker.
     pub picked: ComponentId,
+    /// Map of child score [`Entity`]s scored per target to their best target [`Entity`].
+    ///
+    /// This is filled in while scoring [`Targets`](crate::scoring::Targets) entities.
+    pub best_targets: EntityHashMap<Entity>,
+    /// The target [`Entity`] picked along with the last picked action, if any.
+    pub picked_target: Option<Entity>,
 }
 
 impl Picker {
@@ -87,6 +105,8 @@ impl Picker {
             default,
             choices: EntityHashMap::default(),
             picked: default,
+            best_targets: EntityHashMap::default(),
+            picked_target: None,
         }
     }
 
@@ -98,14 +118,39 @@ impl Picker {
     }
 
     /// Grab the action [`ComponentId`] to pick based on the score [`Entity`] and the picker's choices.
+    ///
+    /// Also records the best target of the score [`Entity`] as [`Picker::picked_target`], if it has one.
     pub fn pick(&mut self, score_entity: Option<Entity>) -> ComponentId {
+        let score_entity = score_entity.filter(|entity| self.choices.contains_key(entity));
         let action = score_entity
             .and_then(|entity| self.choices.get(&entity).copied())
             .unwrap_or(self.default);
         self.picked = action;
+        self.picked_target = score_entity.and_then(|entity| self.best_targets.get(&entity).copied());
+        action
+    }
+
+    /// Picks the action [`ComponentId`] like [`Picker::pick`], and triggers [`OnPicked`] for the picker entity.
+    ///
+    /// Picking observers should use this to trigger [`OnPicked`] exactly once per pick.
+    pub fn pick_and_trigger(
+        &mut self,
+        mut commands: Commands,
+        picker_entity: Entity,
+        score_entity: Option<Entity>,
+    ) -> ComponentId {
+        let action = self.pick(score_entity);
+        let target = self.picked_target;
+        commands.trigger_targets(OnPicked::new(action).with_target(target), picker_entity);
         action
     }
 
+    /// Returns `true` if the given action is the default action or one of the choices.
+    #[must_use]
+    pub fn contains(&self, action: ComponentId) -> bool {
+        self.is_default(action) || self.choices.values().any(|&choice| choice == action)
+    }
+
     /// Returns `true` if the given action is the default action.
     #[must_use]
     pub fn is_default(&self, action: ComponentId) -> bool {
@@ -121,12 +166,12 @@ impl Picker {
 
 #[cfg(test)]
 mod tests {
-    use bevy::prelude::*;
+    use bevy::{ecs::component::ComponentId, prelude::*};
 
     use crate::{
-        event::{RunPicking, RunScoring},
+        event::{OnPicked, RunPicking, RunScoring},
         picking::{FirstToScore, Highest, Picker},
-        scoring::{FixedScore, Score},
+        scoring::{FixedScore, Score, ScoreHierarchy, ScoreOf, ScoringPlugin},
     };
 
     #[derive(Component)]
@@ -182,4 +227,250 @@ mod tests {
 
         assert_eq!(my_action, world.get::<Picker>(actor).unwrap().picked);
     }
+
+    #[test]
+    fn pick_score_of_only() {
+        let mut app = App::new();
+        app.add_plugins(crate::ObservedUtilityPlugins::RealTime.build().set(ScoringPlugin {
+            hierarchy: ScoreHierarchy::Scorers,
+            ..default()
+        }));
+        let world = app.world_mut();
+
+        let my_action = world.register_component::<MyAction>();
+        let idle_action = world.register_component::<IdleAction>();
+
+        let actor = world.spawn(Highest).id();
+        let scorer = world
+            .spawn((FixedScore::new(0.4), Score::default(), ScoreOf(actor)))
+            .id();
+        // Ordinary children aren't picked from, even if they are scored
+        let child = world
+            .spawn((FixedScore::new(0.9), Score::default(), ChildOf(actor)))
+            .id();
+        world.entity_mut(actor).insert(
+            Picker::new(idle_action)
+                .with(scorer, my_action)
+                .with(child, idle_action),
+        );
+
+        world.trigger(RunScoring);
+        world.flush();
+        world.trigger_targets(RunPicking, actor);
+        world.flush();
+
+        assert_eq!(my_action, world.get::<Picker>(actor).unwrap().picked);
+    }
+
+    #[test]
+    fn momentum() {
+        use std::time::Duration;
+
+        use crate::picking::Momentum;
+
+        #[derive(Component)]
+        struct OtherAction;
+
+        let mut app = App::new();
+        app.add_plugins(crate::ObservedUtilityPlugins::TurnBased);
+        let world = app.world_mut();
+        world.init_resource::<Time>();
+
+        let my_action = world.register_component::<MyAction>();
+        let other_action = world.register_component::<OtherAction>();
+        let idle_action = world.register_component::<IdleAction>();
+
+        let mine = world.spawn((FixedScore::new(0.5), Score::default())).id();
+        let other = world.spawn((FixedScore::new(0.4), Score::default())).id();
+        let actor = world
+            .spawn((
+                Picker::new(idle_action).with(mine, my_action).with(other, other_action),
+                Highest,
+                Momentum::new(0.2).with_commitment(Duration::from_secs(2)),
+            ))
+            .add_children(&[mine, other])
+            .id();
+        world.flush();
+
+        let pick = |world: &mut World, other_score: f32, advance: Duration| {
+            world.get_mut::<FixedScore>(other).unwrap().set_value(other_score);
+            world.resource_mut::<Time>().advance_by(advance);
+            world.trigger(RunScoring);
+            world.flush();
+            world.trigger_targets(RunPicking, actor);
+            world.flush();
+            world.get::<Picker>(actor).unwrap().picked
+        };
+
+        assert_eq!(my_action, pick(world, 0.4, Duration::ZERO));
+        // Committed to the current action
+        assert_eq!(my_action, pick(world, 1.0, Duration::from_secs(1)));
+        // Not beating the current action by the bonus
+        assert_eq!(my_action, pick(world, 0.65, Duration::from_secs(2)));
+        // Beating the current action by the bonus
+        assert_eq!(other_action, pick(world, 0.75, Duration::ZERO));
+    }
+
+    #[test]
+    fn momentum_default_uncommitted() {
+        use std::time::Duration;
+
+        use crate::picking::Momentum;
+
+        let mut app = App::new();
+        app.add_plugins(crate::ObservedUtilityPlugins::TurnBased);
+        let world = app.world_mut();
+        world.init_resource::<Time>();
+
+        let my_action = world.register_component::<MyAction>();
+        let idle_action = world.register_component::<IdleAction>();
+
+        let resting = world.spawn((FixedScore::new(0.5), Score::default())).id();
+        let mine = world.spawn((FixedScore::new(0.4), Score::default())).id();
+        let actor = world
+            .spawn((
+                Picker::new(idle_action)
+                    .with(resting, idle_action)
+                    .with(mine, my_action),
+                Highest,
+                Momentum::new(0.2).with_commitment(Duration::from_secs(2)),
+            ))
+            .add_children(&[resting, mine])
+            .id();
+        world.flush();
+
+        let pick = |world: &mut World, my_score: f32, advance: Duration| {
+            world.get_mut::<FixedScore>(mine).unwrap().set_value(my_score);
+            world.resource_mut::<Time>().advance_by(advance);
+            world.trigger(RunScoring);
+            world.flush();
+            world.trigger_targets(RunPicking, actor);
+            world.flush();
+            world.get::<Picker>(actor).unwrap().picked
+        };
+
+        assert_eq!(idle_action, pick(world, 0.4, Duration::ZERO));
+        // Idling first doesn't commit to idling
+        assert_eq!(my_action, pick(world, 1.0, Duration::from_secs(1)));
+        // Committed to the better action
+        assert_eq!(my_action, pick(world, 0.4, Duration::from_secs(1)));
+    }
+
+    /// Scores and picks for a single actor with the given picker component and one choice with the given score.
+    /// Returns every [`OnPicked`] event triggered, along with the choice and default action.
+    fn run_picking(picker: impl Bundle, score: f32) -> (Vec<(Entity, OnPicked)>, Entity, ComponentId, ComponentId) {
+        #[derive(Resource, Default)]
+        struct Picked(Vec<(Entity, OnPicked)>);
+
+        let mut app = App::new();
+        app.add_plugins(crate::ObservedUtilityPlugins::TurnBased);
+        let world = app.world_mut();
+
+        let my_action = world.register_component::<MyAction>();
+        let idle_action = world.register_component::<IdleAction>();
+
+        world.init_resource::<Picked>();
+        world.add_observer(|trigger: Trigger<OnPicked>, mut picked: ResMut<Picked>| {
+            picked.0.push((trigger.target(), *trigger.event()));
+        });
+
+        let scorer = world.spawn((FixedScore::new(score), Score::default())).id();
+        let actor = world
+            .spawn((Picker::new(idle_action).with(scorer, my_action), picker))
+            .add_child(scorer)
+            .id();
+        world.flush();
+
+        world.trigger(RunScoring);
+        world.flush();
+        world.trigger(RunPicking);
+        world.flush();
+
+        let picked = world.remove_resource::<Picked>().unwrap().0;
+        (picked, actor, my_action, idle_action)
+    }
+
+    #[test]
+    fn highest_triggers_on_picked_once() {
+        let (picked, actor, my_action, _) = run_picking(Highest, 0.7);
+        assert_eq!(vec![(actor, OnPicked::new(my_action))], picked);
+    }
+
+    #[test]
+    fn first_to_score_triggers_on_picked_once() {
+        let (picked, actor, my_action, _) = run_picking(FirstToScore::new(0.5), 0.7);
+        assert_eq!(vec![(actor, OnPicked::new(my_action))], picked);
+
+        let (picked, actor, _, idle_action) = run_picking(FirstToScore::new(0.5), 0.3);
+        assert_eq!(vec![(actor, OnPicked::new(idle_action))], picked);
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn random_triggers_on_picked_once() {
+        use rand::{SeedableRng, rngs::StdRng};
+
+        use crate::picking::PickRandom;
+
+        let (picked, actor, my_action, _) = run_picking(PickRandom::new(StdRng::seed_from_u64(0)), 0.7);
+        assert_eq!(vec![(actor, OnPicked::new(my_action))], picked);
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn weighted_random_triggers_on_picked_once() {
+        use rand::{SeedableRng, rngs::StdRng};
+
+        use crate::picking::PickWeightedRandom;
+
+        let (picked, actor, my_action, _) = run_picking(PickWeightedRandom::new(StdRng::seed_from_u64(0)), 0.7);
+        assert_eq!(vec![(actor, OnPicked::new(my_action))], picked);
+
+        let (picked, actor, _, idle_action) = run_picking(PickWeightedRandom::new(StdRng::seed_from_u64(0)), 0.);
+        assert_eq!(vec![(actor, OnPicked::new(idle_action))], picked);
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn weighted_random_distribution() {
+        use rand::{SeedableRng, rngs::StdRng};
+
+        use crate::picking::{PickCutoff, PickWeightedRandom};
+
+        let mut world = World::new();
+        let [low, mid, high] = [(); 3].map(|_| world.spawn_empty().id());
+        let candidates = vec![(low, 0.1), (mid, 0.3), (high, 0.6)];
+
+        let count = |mut picker: PickWeightedRandom| {
+            let mut counts = [0; 3];
+            for _ in 0..10_000 {
+                let picked = picker.choose(candidates.clone()).unwrap();
+                counts[[low, mid, high].iter().position(|&e| e == picked).unwrap()] += 1;
+            }
+            counts
+        };
+
+        // Proportional to scores
+        let [l, m, h] = count(PickWeightedRandom::new(StdRng::seed_from_u64(0)));
+        assert!((800..1200).contains(&l), "{l}");
+        assert!((2700..3300).contains(&m), "{m}");
+        assert!((5700..6300).contains(&h), "{h}");
+
+        // Low temperatures favor the highest score
+        let [l, m, h] = count(PickWeightedRandom::new(StdRng::seed_from_u64(0)).with_temperature(0.05));
+        assert!(l < 10 && m < 300 && h > 9700, "{l} {m} {h}");
+
+        // Zero temperature always picks the highest score
+        let [_, _, h] = count(PickWeightedRandom::new(StdRng::seed_from_u64(0)).with_temperature(0.));
+        assert_eq!(10_000, h);
+
+        // Cutoffs remove the lowest scores
+        let [l, m, _] = count(PickWeightedRandom::new(StdRng::seed_from_u64(0)).with_cutoff(PickCutoff::TopN(2)));
+        assert_eq!(0, l);
+        assert!((3000..3700).contains(&m), "{m}");
+
+        let [l, m, _] =
+            count(PickWeightedRandom::new(StdRng::seed_from_u64(0)).with_cutoff(PickCutoff::WithinOfBest(0.1)));
+        assert_eq!((0, 0), (l, m));
+    }
 }
@@ -5,9 +5,9 @@ use bevy::{
 
 use crate::{
     ecs::{CommandsExt, TriggerGetEntity},
-    event::{OnPick, OnPicked},
-    picking::Picker,
-    scoring::Score,
+    event::OnPick,
+    picking::{Momentum, Picker, pick_with_momentum},
+    scoring::{Score, ScoreTree},
 };
 
 /// [`Picker`] [`Component`] that picks the first [`Score`] entity to reach a certain threshold.
@@ -87,37 +87,63 @@ impl FirstToScore {
     fn observer(
         trigger: Trigger<OnPick>,
         mut commands: Commands,
-        mut targets: Query<(Entity, &Children, &mut Picker, &FirstToScore)>,
+        mut targets: Query<(Entity, &mut Picker, &FirstToScore, Option<&mut Momentum>)>,
         scores: Query<(Entity, &Score)>,
+        tree: ScoreTree,
+        time: Option<Res<Time>>,
     ) {
         fn run(
             target: Entity,
-            mut commands: Commands,
-            children: &Children,
+            commands: Commands,
+            mut children_scores: impl Iterator<Item = (Entity, Score)>,
             mut picker: Mut<Picker>,
             settings: &FirstToScore,
-            scores: &Query<(Entity, &Score)>,
+            momentum: Option<Mut<Momentum>>,
+            time: Option<&Time>,
         ) {
-            for (score_entity, score) in scores.iter_many(children) {
-                if *score >= settings.threshold() {
-                    picker.pick(Some(score_entity));
-                    return;
-                }
-            }
-
-            // If no score entity reached the threshold, pick the default action
-            let action = picker.pick(None);
-            commands.trigger_targets(OnPicked { action }, target);
+            // If no score entity reached the threshold, the default action is picked
+            pick_with_momentum(commands, target, &mut picker, momentum, time, |_, score_of| {
+                children_scores
+                    .find(|(score_entity, score)| score_of(*score_entity, *score) >= settings.threshold().get())
+                    .map(|(score_entity, _)| score_entity)
+            });
         }
 
+        let time = time.as_deref();
         if let Some(target) = trigger.get_entity() {
-            let Ok((target, children, picker, settings)) = targets.get_mut(target) else {
+            let Ok((target, picker, settings, momentum)) = targets.get_mut(target) else {
                 return;
             };
-            run(target, commands.reborrow(), children, picker, settings, &scores);
+            let children = tree.children(target);
+            if children.is_empty() {
+                return;
+            }
+            let children_scores = scores.iter_many(children).map(|(entity, score)| (entity, *score));
+            run(
+                target,
+                commands.reborrow(),
+                children_scores,
+                picker,
+                settings,
+                momentum,
+                time,
+            );
         } else {
-            for (target, children, picker, settings) in &mut targets {
-                run(target, commands.reborrow(), children, picker, settings, &scores);
+            for (target, picker, settings, momentum) in &mut targets {
+                let children = tree.children(target);
+                if children.is_empty() {
+                    continue;
+                }
+                let children_scores = scores.iter_many(children).map(|(entity, score)| (entity, *score));
+                run(
+                    target,
+                    commands.reborrow(),
+                    children_scores,
+                    picker,
+                    settings,
+                    momentum,
+                    time,
+                );
             }
         }
     }
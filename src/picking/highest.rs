@@ -5,9 +5,9 @@ use bevy::{
 
 use crate::{
     ecs::{CommandsExt, TriggerGetEntity},
-    event::{OnPick, OnPicked},
-    picking::Picker,
-    scoring::Score,
+    event::OnPick,
+    picking::{Momentum, Picker, pick_with_momentum},
+    scoring::{Score, ScoreTree},
 };
 
 /// [`Picker`] [`Component`] that picks the highest [`Score`](crate::scoring::Score).
@@ -64,39 +64,54 @@ impl Highest {
     fn observer(
         trigger: Trigger<OnPick>,
         mut commands: Commands,
-        mut targets: Query<(Entity, &Children, &mut Picker), With<Highest>>,
+        mut targets: Query<(Entity, &mut Picker, Option<&mut Momentum>), With<Highest>>,
         scores: Query<(Entity, &Score)>,
+        tree: ScoreTree,
+        time: Option<Res<Time>>,
     ) {
         fn run(
             target: Entity,
-            mut commands: Commands,
-            children: &Children,
+            commands: Commands,
+            children_scores: impl Iterator<Item = (Entity, Score)>,
             mut picker: Mut<Picker>,
-            scores: &Query<(Entity, &Score)>,
+            momentum: Option<Mut<Momentum>>,
+            time: Option<&Time>,
         ) {
-            let mut highest_score_entity: Option<(Entity, &Score)> = None;
-            for (score_entity, score) in scores.iter_many(children) {
-                if let Some((_, highest_score)) = highest_score_entity {
-                    if score.get() > highest_score.get() {
+            pick_with_momentum(commands, target, &mut picker, momentum, time, |_, score_of| {
+                let mut highest_score_entity: Option<(Entity, f32)> = None;
+                for (score_entity, score) in children_scores {
+                    let score = score_of(score_entity, score);
+                    if let Some((_, highest_score)) = highest_score_entity {
+                        if score > highest_score {
+                            highest_score_entity = Some((score_entity, score));
+                        }
+                    } else {
                         highest_score_entity = Some((score_entity, score));
                     }
-                } else {
-                    highest_score_entity = Some((score_entity, score));
                 }
-            }
-
-            let action = picker.pick(highest_score_entity.map(|(entity, _)| entity));
-            commands.trigger_targets(OnPicked { action }, target);
+                highest_score_entity.map(|(entity, _)| entity)
+            });
         }
 
+        let time = time.as_deref();
         if let Some(target) = trigger.get_entity() {
-            let Ok((target, children, picker)) = targets.get_mut(target) else {
+            let Ok((target, picker, momentum)) = targets.get_mut(target) else {
                 return;
             };
-            run(target, commands.reborrow(), children, picker, &scores);
+            let children = tree.children(target);
+            if children.is_empty() {
+                return;
+            }
+            let children_scores = scores.iter_many(children).map(|(entity, score)| (entity, *score));
+            run(target, commands.reborrow(), children_scores, picker, momentum, time);
         } else {
-            for (target, children, picker) in &mut targets {
-                run(target, commands.reborrow(), children, picker, &scores);
+            for (target, picker, momentum) in &mut targets {
+                let children = tree.children(target);
+                if children.is_empty() {
+                    continue;
+                }
+                let children_scores = scores.iter_many(children).map(|(entity, score)| (entity, *score));
+                run(target, commands.reborrow(), children_scores, picker, momentum, time);
             }
         }
     }
@@ -0,0 +1,160 @@
+use std::time::Duration;
+
+use bevy::{ecs::component::ComponentId, prelude::*};
+
+use crate::{event::OnPicked, picking::Picker, scoring::Score};
+
+/// [`Component`] for [`Picker`] entities that favors the currently picked action, to prevent flip-flopping
+/// between actions whose scores hover around each other.
+///
+/// - The [`bonus`](Momentum::bonus) is added to the score of the choice mapped to the [`Picker::picked`] action,
+///   so other choices must beat it by that margin.
+/// - The [`commitment`](Momentum::commitment) is the minimum time to keep a picked action before switching,
+///   measured with the [`Time`] [`Resource`]. Without it, there is no commitment.
+///
+/// Supported by the score-based pickers: [`Highest`], [`FirstToScore`] and `PickWeightedRandom`.
+///
+/// # Example
+///
+/// ```rust
+/// use std::time::Duration;
+///
+/// use bevy::prelude::*;
+/// use bevy_observed_utility::prelude::*;
+///
+/// # let mut app = App::new();
+/// # app.add_plugins(ObservedUtilityPlugins::RealTime);
+/// # let mut world = app.world_mut();
+/// #[derive(Component)]
+/// pub struct Walk;
+/// #[derive(Component)]
+/// pub struct Run;
+/// #[derive(Component)]
+/// pub struct IdleAction;
+///
+/// let walk = world.register_component::<Walk>();
+/// let run = world.register_component::<Run>();
+/// let idle_action = world.register_component::<IdleAction>();
+///
+/// let walking = world.spawn((FixedScore::new(0.5), Score::default())).id();
+/// let running = world.spawn((FixedScore::new(0.55), Score::default())).id();
+/// let actor = world
+///     .spawn((
+///         Picker::new(idle_action).with(walking, walk).with(running, run),
+///         Highest,
+///         // Only switch if another action scores 0.1 higher, and keep it for at least 2 seconds.
+///         Momentum::new(0.1).with_commitment(Duration::from_secs(2)),
+///     ))
+///     .add_children(&[walking, running])
+///     .id();
+/// # world.flush();
+///
+/// world.trigger(RunScoring);
+/// world.trigger_targets(RunPicking, actor);
+/// # world.flush();
+/// assert_eq!(run, world.get::<Picker>(actor).unwrap().picked);
+///
+/// // Walking now scores higher, but not high enough to switch.
+/// world.get_mut::<FixedScore>(walking).unwrap().set_value(0.6);
+/// world.trigger(RunScoring);
+/// world.trigger_targets(RunPicking, actor);
+/// # world.flush();
+/// assert_eq!(run, world.get::<Picker>(actor).unwrap().picked);
+/// ```
+///
+/// [`Highest`]: crate::picking::Highest
+/// [`FirstToScore`]: crate::picking::FirstToScore
+#[derive(Component, Reflect)]
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+#[reflect(Component, PartialEq, Debug, Default)]
+pub struct Momentum {
+    /// The bonus added to the score of the choice mapped to the picked action.
+    pub bonus: f32,
+    /// The minimum time to keep a picked action before switching to another one.
+    pub commitment: Duration,
+    /// The elapsed [`Time`] when the picked action last changed.
+    switched_at: Option<Duration>,
+}
+
+impl Momentum {
+    /// Creates a new [`Momentum`] with the given bonus and no commitment.
+    #[must_use]
+    pub fn new(bonus: f32) -> Self {
+        Self {
+            bonus,
+            commitment: Duration::ZERO,
+            switched_at: None,
+        }
+    }
+
+    /// Sets the minimum time to keep a picked action before switching to another one.
+    #[must_use]
+    pub fn with_commitment(mut self, commitment: Duration) -> Self {
+        self.commitment = commitment;
+        self
+    }
+
+    /// Returns the given score of the score entity, plus the bonus if it's mapped to the picked action.
+    #[must_use]
+    pub fn score(&self, picker: &Picker, score_entity: Entity, score: Score) -> f32 {
+        if picker.choices.get(&score_entity) == Some(&picker.picked) {
+            score.get() + self.bonus
+        } else {
+            score.get()
+        }
+    }
+
+    /// Returns `true` if the picked action must still be kept at the given elapsed time.
+    #[must_use]
+    pub fn is_committed(&self, now: Option<Duration>) -> bool {
+        match (self.switched_at, now) {
+            (Some(switched_at), Some(now)) => now.saturating_sub(switched_at) < self.commitment,
+            _ => false,
+        }
+    }
+
+    /// Records the newly picked action of the picker, restarting the commitment if it's different from the previous one.
+    ///
+    /// There is no commitment to the [`Picker::default`] action, so any other action can take over right away.
+    pub fn record(&mut self, previous: ComponentId, picker: &Picker, now: Option<Duration>) {
+        if picker.picked == picker.default {
+            self.switched_at = None;
+        } else if picker.picked != previous {
+            self.switched_at = now;
+        }
+    }
+}
+
+/// Picks an action for the picker entity and triggers [`OnPicked`], taking its [`Momentum`] into account.
+///
+/// The `choose` function receives the picker and a function returning the score of a score entity
+/// including the momentum bonus, and returns the score entity to pick.
+pub(crate) fn pick_with_momentum(
+    mut commands: Commands,
+    picker_entity: Entity,
+    picker: &mut Picker,
+    momentum: Option<Mut<Momentum>>,
+    time: Option<&Time>,
+    choose: impl FnOnce(&Picker, &dyn Fn(Entity, Score) -> f32) -> Option<Entity>,
+) -> ComponentId {
+    let Some(mut momentum) = momentum else {
+        let picked = choose(picker, &|_, score| score.get());
+        return picker.pick_and_trigger(commands, picker_entity, picked);
+    };
+
+    let now = time.map(Time::elapsed);
+    let previous = picker.picked;
+    if momentum.is_committed(now) {
+        // Keep the current action
+        let target = picker.picked_target;
+        commands.trigger_targets(OnPicked::new(previous).with_target(target), picker_entity);
+        return previous;
+    }
+
+    let picked = choose(picker, &|score_entity, score| {
+        momentum.score(picker, score_entity, score)
+    });
+    let action = picker.pick_and_trigger(commands, picker_entity, picked);
+    momentum.record(previous, picker, now);
+    action
+}
@@ -1,12 +1,12 @@
 use bevy::{
-    ecs::component::{ComponentHooks, StorageType},
+    ecs::component::{ComponentHooks, Mutable, StorageType},
     prelude::*,
 };
-use rand::{seq::IteratorRandom, RngCore};
+use rand::{RngCore, seq::IteratorRandom};
 
 use crate::{
     ecs::{CommandsExt, TriggerGetEntity},
-    event::{OnPick, OnPicked},
+    event::OnPick,
     picking::Picker,
 };
 
@@ -29,8 +29,8 @@ use crate::{
 ///
 /// // We need the ComponentIds to initialize the Picker.
 /// // It's recommended to use a resource to store these.
-/// let my_action = world.init_component::<MyAction>();
-/// let idle_action = world.init_component::<IdleAction>();
+/// let my_action = world.register_component::<MyAction>();
+/// let idle_action = world.register_component::<IdleAction>();
 ///
 /// # let mut commands = world.commands();
 /// // Spawn the scorer entity that will be picked by the actor.
@@ -45,7 +45,7 @@ use crate::{
 ///         Picker::new(idle_action)
 ///             // if the score entity is selected, my_action will be picked.
 ///             .with(scorer, my_action),
-///         PickRandom::new(StdRng::from_entropy()),
+///         PickRandom::new(StdRng::from_os_rng()),
 ///     ))
 ///     .add_child(scorer)
 ///     .id();
@@ -61,7 +61,7 @@ pub struct PickRandom {
 }
 
 impl PickRandom {
-    /// Creates a new [`Random`] with the given random number generator.
+    /// Creates a new [`PickRandom`] with the given random number generator.
     pub fn new(rng: impl RngCore + Send + Sync + 'static) -> Self {
         Self { rng: Box::new(rng) }
     }
@@ -76,16 +76,15 @@ impl PickRandom {
         self.rng = Box::new(rng);
     }
 
-    /// [`Observer`] for the [`Random`] [`Picker`] that picks randomly.
+    /// [`Observer`] for the [`PickRandom`] [`Picker`] that picks randomly.
     fn observer(
         trigger: Trigger<OnPick>,
         mut commands: Commands,
         mut targets: Query<(Entity, &mut Picker, &mut PickRandom)>,
     ) {
-        fn run(target: Entity, mut commands: Commands, mut picker: Mut<Picker>, settings: &mut PickRandom) {
+        fn run(target: Entity, commands: Commands, mut picker: Mut<Picker>, settings: &mut PickRandom) {
             let random = picker.choices.keys().choose(&mut *settings.rng()).copied();
-            let action = picker.pick(random);
-            commands.trigger_targets(OnPicked { action }, target);
+            picker.pick_and_trigger(commands, target, random);
         }
 
         if let Some(target) = trigger.get_entity() {
@@ -108,10 +107,11 @@ impl<R: RngCore + Send + Sync + 'static> From<R> for PickRandom {
 }
 
 impl Component for PickRandom {
+    type Mutability = Mutable;
     const STORAGE_TYPE: StorageType = StorageType::Table;
 
     fn register_component_hooks(hooks: &mut ComponentHooks) {
-        hooks.on_add(|mut world, _entity, _component| {
+        hooks.on_add(|mut world, _ctx| {
             #[derive(Resource, Default)]
             struct RandomObserverSpawned;
 
@@ -0,0 +1,261 @@
+use bevy::{
+    ecs::component::{ComponentHooks, Mutable, StorageType},
+    prelude::*,
+};
+use rand::{Rng, RngCore};
+
+use crate::{
+    ecs::{CommandsExt, TriggerGetEntity},
+    event::OnPick,
+    picking::{Momentum, Picker, pick_with_momentum},
+    scoring::{Score, ScoreTree},
+};
+
+/// [`Picker`] [`Component`] that picks randomly, with a probability proportional to each choice's [`Score`].
+///
+/// - With a [`temperature`](PickWeightedRandom::temperature), choices are weighted by the softmax of their scores instead.
+///   Lower temperatures favor the highest scores, higher temperatures approach a uniform pick.
+/// - With a [`cutoff`](PickWeightedRandom::cutoff), only the best scoring choices are considered.
+///
+/// If no choice has a positive weight, the default action is picked.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_observed_utility::prelude::*;
+/// use rand::prelude::{StdRng, SeedableRng};
+///
+/// # let mut app = App::new();
+/// # app.add_plugins(ObservedUtilityPlugins::RealTime);
+/// # let mut world = app.world_mut();
+/// #[derive(Component)]
+/// pub struct MyAction;
+/// #[derive(Component)]
+/// pub struct IdleAction;
+///
+/// // We need the ComponentIds to initialize the Picker.
+/// // It's recommended to use a resource to store these.
+/// let my_action = world.register_component::<MyAction>();
+/// let idle_action = world.register_component::<IdleAction>();
+///
+/// # let mut commands = world.commands();
+/// // Spawn the scorer entity that will be picked by the actor.
+/// let scorer = commands
+///     .spawn((FixedScore::new(0.7), Score::default()))
+///     .id();
+///
+/// // Spawn the actor entity that will pick an action based on all of its children scores.
+/// let actor = commands
+///     .spawn((
+///         // All pickers need a default action to pick if they fail to pick an action.
+///         Picker::new(idle_action)
+///             // if the score entity is selected, my_action will be picked.
+///             .with(scorer, my_action),
+///         PickWeightedRandom::new(StdRng::from_os_rng())
+///             .with_temperature(0.1)
+///             .with_cutoff(PickCutoff::TopN(3)),
+///     ))
+///     .add_child(scorer)
+///     .id();
+///
+/// commands.trigger_targets(RunScoring, scorer);
+/// commands.trigger_targets(RunPicking, actor);
+/// # world.flush();
+/// # assert_eq!(my_action, world.get::<Picker>(actor).unwrap().picked);
+/// ```
+pub struct PickWeightedRandom {
+    /// The random number generator to use.
+    pub rng: Box<dyn RngCore + Send + Sync + 'static>,
+    /// The softmax temperature, or `None` to weight choices by their scores directly.
+    ///
+    /// A non-positive temperature always picks the highest score.
+    pub temperature: Option<f32>,
+    /// Which choices to consider, based on their scores.
+    pub cutoff: PickCutoff,
+}
+
+impl PickWeightedRandom {
+    /// Creates a new [`PickWeightedRandom`] with the given random number generator,
+    /// weighting all choices by their scores.
+    #[must_use]
+    pub fn new(rng: impl RngCore + Send + Sync + 'static) -> Self {
+        Self {
+            rng: Box::new(rng),
+            temperature: None,
+            cutoff: PickCutoff::None,
+        }
+    }
+
+    /// Weights the choices by the softmax of their scores, with the given temperature.
+    #[must_use]
+    pub fn with_temperature(mut self, temperature: f32) -> Self {
+        self.temperature = Some(temperature);
+        self
+    }
+
+    /// Only considers the choices within the given cutoff.
+    #[must_use]
+    pub fn with_cutoff(mut self, cutoff: PickCutoff) -> Self {
+        self.cutoff = cutoff;
+        self
+    }
+
+    /// Returns a mutable reference to the random number generator.
+    #[must_use]
+    pub fn rng_mut(&mut self) -> &mut (impl RngCore + Send + Sync + 'static) {
+        &mut self.rng
+    }
+
+    /// Sets the random number generator.
+    pub fn set_rng(&mut self, rng: impl RngCore + Send + Sync + 'static) {
+        self.rng = Box::new(rng);
+    }
+
+    /// Randomly picks one of the given score entities, based on their scores and the settings.
+    ///
+    /// Returns `None` if no score entity has a positive weight.
+    pub fn choose(&mut self, mut candidates: Vec<(Entity, f32)>) -> Option<Entity> {
+        // Best scores first, keeping the order of equal scores
+        candidates.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+        let best = candidates.first()?.1;
+
+        match self.cutoff {
+            PickCutoff::None => {}
+            PickCutoff::TopN(n) => candidates.truncate(n),
+            PickCutoff::WithinOfBest(range) => candidates.retain(|(_, score)| *score >= best - range),
+        }
+
+        let weight = |score: f32| match self.temperature {
+            None => score.max(0.),
+            Some(temperature) if temperature > 0. => ((score - best) / temperature).exp(),
+            // Zero temperature, only the best remain
+            Some(_) if score >= best => 1.,
+            Some(_) => 0.,
+        };
+
+        let total: f32 = candidates.iter().map(|(_, score)| weight(*score)).sum();
+        if total <= 0. || !total.is_finite() {
+            return None;
+        }
+
+        let mut remaining = self.rng.random_range(0.0..total);
+        let mut picked = None;
+        for (entity, score) in candidates {
+            let weight = weight(score);
+            if weight <= 0. {
+                continue;
+            }
+            picked = Some(entity);
+            remaining -= weight;
+            if remaining < 0. {
+                break;
+            }
+        }
+        picked
+    }
+
+    /// [`Observer`] for the [`PickWeightedRandom`] [`Picker`] that picks randomly, weighted by score.
+    fn observer(
+        trigger: Trigger<OnPick>,
+        mut commands: Commands,
+        mut targets: Query<(Entity, &mut Picker, &mut PickWeightedRandom, Option<&mut Momentum>)>,
+        scores: Query<(Entity, &Score)>,
+        tree: ScoreTree,
+        time: Option<Res<Time>>,
+    ) {
+        fn run(
+            target: Entity,
+            commands: Commands,
+            children_scores: impl Iterator<Item = (Entity, Score)>,
+            mut picker: Mut<Picker>,
+            settings: &mut PickWeightedRandom,
+            momentum: Option<Mut<Momentum>>,
+            time: Option<&Time>,
+        ) {
+            pick_with_momentum(commands, target, &mut picker, momentum, time, |picker, score_of| {
+                let candidates = children_scores
+                    .filter(|(score_entity, _)| picker.choices.contains_key(score_entity))
+                    .map(|(score_entity, score)| (score_entity, score_of(score_entity, score)))
+                    .collect();
+
+                settings.choose(candidates)
+            });
+        }
+
+        let time = time.as_deref();
+        if let Some(target) = trigger.get_entity() {
+            let Ok((target, picker, settings, momentum)) = targets.get_mut(target) else {
+                return;
+            };
+            let children = tree.children(target);
+            if children.is_empty() {
+                return;
+            }
+            let children_scores = scores.iter_many(children).map(|(entity, score)| (entity, *score));
+            run(
+                target,
+                commands.reborrow(),
+                children_scores,
+                picker,
+                settings.into_inner(),
+                momentum,
+                time,
+            );
+        } else {
+            for (target, picker, settings, momentum) in targets.iter_mut() {
+                let children = tree.children(target);
+                if children.is_empty() {
+                    continue;
+                }
+                let children_scores = scores.iter_many(children).map(|(entity, score)| (entity, *score));
+                run(
+                    target,
+                    commands.reborrow(),
+                    children_scores,
+                    picker,
+                    settings.into_inner(),
+                    momentum,
+                    time,
+                );
+            }
+        }
+    }
+}
+
+impl<R: RngCore + Send + Sync + 'static> From<R> for PickWeightedRandom {
+    fn from(rng: R) -> Self {
+        Self::new(rng)
+    }
+}
+
+impl Component for PickWeightedRandom {
+    type Mutability = Mutable;
+    const STORAGE_TYPE: StorageType = StorageType::Table;
+
+    fn register_component_hooks(hooks: &mut ComponentHooks) {
+        hooks.on_add(|mut world, _ctx| {
+            #[derive(Resource, Default)]
+            struct WeightedRandomObserverSpawned;
+
+            world
+                .commands()
+                .once::<WeightedRandomObserverSpawned>()
+                .observe(Self::observer);
+        });
+    }
+}
+
+/// Which choices [`PickWeightedRandom`] considers, based on their scores.
+#[derive(Reflect)]
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+#[reflect(PartialEq, Debug, Default)]
+pub enum PickCutoff {
+    /// Consider all choices.
+    #[default]
+    None,
+    /// Only consider the N highest scoring choices.
+    TopN(usize),
+    /// Only consider the choices scoring within the given range of the highest score.
+    WithinOfBest(f32),
+}
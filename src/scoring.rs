@@ -11,60 +11,186 @@
 //! - [`Sum`]: Scores the sum of all child scores.
 //! - [`Winning`]: Scores the highest child score.
 //!
+//! # Custom scorers
+//!
+//! Derive [`Scorer`] to implement [`Component`] for a custom scorer, spawning its [`Observer`]s only once.
+//! Scorers that only aggregate their child scores can implement [`ChildAggregateScorer`] instead of writing an observer.
+//!
+//! # Provided post-processing
+//!
+//! - [`PostEvaluated`]: Curves the entity's own freshly calculated score with an [`Evaluator`].
+//! - [`Cooldown`]: Zeroes the entity's score for a while after its mapped action has ended.
+//!
 //! # Provided [`Observer`] utilities
 //!
 //! - [`score_ancestor`]: Does the busy work of scoring a child entity based on its closest ancestor entity with a given component.
+//! - [`score_target`]: Does the busy work of scoring an entity based on the target entity currently scored by [`Targets`].
+//!
+//! # Scoring trees
+//!
+//! Scoring trees are made up of [`ChildOf`]/[`Children`] or [`ScoreOf`]/[`Scorers`] relationships.
+//! [`ScoreOf`] keeps scoring trees apart from other hierarchies, so that actors can have ordinary children.
+//! Set [`ScoringPlugin::hierarchy`] to [`ScoreHierarchy::Scorers`] once all trees have been migrated to it.
+//!
+//! # Per-target scoring
+//!
+//! [`Targets`] entities are scored once per candidate target entity, to pick who to act on along with what to do.
+//! The best target is recorded in the parent [`Picker`](crate::picking::Picker) and picked along with the action.
+//!
+//! # History
+//!
+//! Add [`ScoreHistory`] to a [`Score`] entity to keep its last few scores,
+//! which can be exported as CSV for all of an actor's score entities.
+//!
+//! # Tracing
+//!
+//! Add [`ScoringTrace`] (requires `trace` feature) to an actor entity to record how its last pick was scored,
+//! which can be pretty-printed as an indented tree or exported as JSON.
+//!
+//! # Incremental scoring
+//!
+//! Scoring trees whose inputs change rarely can skip re-scoring clean entities by enabling
+//! [`ScoringPlugin::incremental`]. Mark scorers that read their inputs from elsewhere as [`Volatile`].
+//!
+//! # Parallel scoring
+//!
+//! Independent scoring trees can be scored in parallel by enabling [`ScoringPlugin::parallel`].
+//! Scorers opt into this by implementing [`ParallelScorer`], which all of the provided scorers (except `RandomScore`) do.
+//! Only trees whose roots are marked with [`SkipScoringEvents`] are scored in parallel, as they don't trigger
+//! [`OnScore`] or [`OnScorePostProcess`]. See [`ScoringPlugin::parallel`].
 
 use std::{
     cmp::Ordering,
     ops::{Bound, RangeBounds},
 };
 
-use bevy::prelude::*;
+use bevy::{
+    ecs::{component::HookContext, relationship::Relationship, world::DeferredWorld},
+    prelude::*,
+    reflect::{
+        ReflectFromReflect, TypeRegistration, TypeRegistry,
+        serde::{ReflectDeserializer, ReflectSerializer},
+    },
+};
+use serde::{Deserializer, Serialize, Serializer, de::DeserializeSeed, de::Error as _, ser::Error as _};
+
+pub use bevy_observed_utility_derive::Scorer;
 
 use crate::{
     ecs::{AncestorQuery, DFSPostTraversal, TriggerGetEntity},
-    event::{OnScore, RunScoring},
+    event::{OnScore, OnScorePostProcess, RunScoring},
 };
 
+mod aggregate;
 mod all_or_nothing;
+mod cooldown;
 mod evaluator;
 mod fixed;
+mod hierarchy;
+mod history;
+mod incremental;
 mod measured;
+mod parallel;
 mod product;
 #[cfg(feature = "rand")]
 mod random;
 mod sum;
+mod targets;
+#[cfg(feature = "trace")]
+mod trace;
 mod winning;
 
+pub use self::aggregate::*;
 pub use self::all_or_nothing::*;
+pub use self::cooldown::*;
 pub use self::evaluator::*;
 pub use self::fixed::*;
+pub use self::hierarchy::*;
+pub use self::history::*;
+pub use self::incremental::*;
 pub use self::measured::*;
+pub use self::parallel::*;
 pub use self::product::*;
 #[cfg(feature = "rand")]
 pub use self::random::*;
 pub use self::sum::*;
+pub use self::targets::*;
+#[cfg(feature = "trace")]
+pub use self::trace::*;
 pub use self::winning::*;
 
 /// [`Plugin`] for scoring entities.
 #[derive(Default)]
-pub struct ScoringPlugin;
+pub struct ScoringPlugin {
+    /// Whether to score independent scoring trees in parallel, instead of one entity at a time.
+    ///
+    /// Trees are only scored in parallel if their roots are marked with [`SkipScoringEvents`],
+    /// as **[`OnScore`] and [`OnScorePostProcess`] are NOT triggered for trees scored in parallel.**
+    /// They also have to be made up entirely of [`ParallelScorer`]s (and optionally [`ParallelPostProcessor`]s).
+    /// All other trees are scored one entity at a time as usual, triggering both events.
+    /// Components whose observers need to run can be registered with [`ParallelScorers::register_serial_only`],
+    /// which makes their trees scored as usual even if they're marked.
+    /// Children are always scored before their parents.
+    ///
+    /// Custom scorers can be registered with [`ParallelScorers::register_scorer`].
+    ///
+    /// Defaults to `false`.
+    pub parallel: bool,
+    /// Which relationships make up scoring trees, see [`ScoreHierarchy`].
+    ///
+    /// Defaults to [`ScoreHierarchy::ScorersOrChildren`], so trees can be migrated to [`ScoreOf`] gradually.
+    pub hierarchy: ScoreHierarchy,
+    /// Whether to only re-score the parts of scoring trees that may have changed since they were last scored.
+    ///
+    /// A [`Score`] entity is re-scored if any of its components changed, if any of its children's scores changed,
+    /// or if it's [`Volatile`]. Clean entities keep their score without triggering [`OnScore`] or
+    /// [`OnScorePostProcess`]. Scorers that read their inputs from elsewhere, like the actor entity,
+    /// need to be marked [`Volatile`] or registered with [`IncrementalScorers::register_volatile`].
+    ///
+    /// This has no effect if [`ScoringPlugin::parallel`] is enabled.
+    ///
+    /// Defaults to `false`.
+    pub incremental: bool,
+}
 
 impl Plugin for ScoringPlugin {
     fn build(&self, app: &mut App) {
-        app.add_observer(Self::run_scoring_post_order_dfs);
+        if self.parallel {
+            app.init_resource::<ParallelScorers>()
+                .add_observer(Self::run_scoring_parallel);
+        } else if self.incremental {
+            app.init_resource::<IncrementalScorers>()
+                .add_observer(Self::run_scoring_incremental);
+        } else {
+            app.add_observer(Self::run_scoring_post_order_dfs);
+        }
+
+        app.insert_resource(self.hierarchy)
+            .add_observer(Self::invalidate_scoring_order_on_parent_change::<OnInsert, ChildOf>)
+            .add_observer(Self::invalidate_scoring_order_on_parent_change::<OnReplace, ChildOf>)
+            .add_observer(Self::invalidate_scoring_order_on_parent_change::<OnInsert, ScoreOf>)
+            .add_observer(Self::invalidate_scoring_order_on_parent_change::<OnReplace, ScoreOf>);
 
         app.register_type::<Score>()
+            .register_type::<ScoringOrder>()
+            .register_type::<ScoreOf>()
+            .register_type::<Scorers>()
+            .register_type::<ScoreHierarchy>()
+            .register_type::<Volatile>()
+            .register_type::<SkipScoringEvents>()
+            .register_type::<LastScored>()
             .register_type::<AllOrNothing>()
-            // .register_type::<Evaluated>() // TODO: Implement reflection for Evaluated
+            .register_type::<Evaluated>()
+            .register_type::<PostEvaluated>()
             .register_type::<LinearEvaluator>()
             .register_type::<PowerEvaluator>()
             .register_type::<SigmoidEvaluator>()
             .register_type::<ExponentialEvaluator>()
             .register_type::<LogarithmicEvaluator>()
+            .register_type::<PiecewiseLinearEvaluator>()
+            .register_type::<CubicSplineEvaluator>()
             .register_type::<FixedScore>()
-            // .register_type::<Measured>() // TODO: Implement reflection for Measured
+            .register_type::<Measured>()
             .register_type::<Weighted>()
             .register_type::<WeightedSum>()
             .register_type::<WeightedProduct>()
@@ -72,61 +198,251 @@ impl Plugin for ScoringPlugin {
             .register_type::<WeightedRMS>()
             .register_type::<Product>()
             .register_type::<Sum>()
-            .register_type::<Winning>();
-
-        #[cfg(feature = "rand")]
-        app.register_type::<RandomScore>();
-
-        app.register_type::<RunScoring>().register_type::<OnScore>();
+            .register_type::<Winning>()
+            .register_type::<Cooldown>()
+            .register_type::<CooldownLength>()
+            .register_type::<Targets>()
+            .register_type::<ScoreHistory>()
+            .register_type::<ScoreClock>()
+            .register_type::<ScoreTimestamp>();
+
+        #[cfg(feature = "trace")]
+        app.register_type::<ScoringTrace>()
+            .register_type::<TraceNode>()
+            .register_type::<TracePick>();
+
+        app.register_type::<RunScoring>()
+            .register_type::<OnScore>()
+            .register_type::<OnScorePostProcess>();
     }
 }
 
 impl ScoringPlugin {
     /// For each scoreable root entity, perform post-order depth-first traversal,
-    /// triggering [`OnScore`] for each entity on the way back up.
+    /// triggering [`OnScore`] and then [`OnScorePostProcess`] for each entity on the way back up.
+    ///
+    /// The traversal is cached on each root entity as a [`ScoringOrder`].
     pub fn run_scoring_post_order_dfs(
         trigger: Trigger<RunScoring>,
         mut commands: Commands,
-        scoreable_roots: Query<(Entity, Option<&ChildOf>), With<Score>>,
-        root_parents: Query<(), Without<Score>>,
-        mut dfs: DFSPostTraversal<With<Score>>,
+        roots: ScoreRoots,
+        orders: Query<&ScoringOrder>,
+        per_target: Query<(), With<Targets>>,
+        mut dfs: DFSPostTraversal<(With<Score>, Without<Targets>)>,
     ) {
-        fn trigger_in_order(root: Entity, mut commands: Commands, dfs: &mut DFSPostTraversal<With<Score>>) {
-            let sorted = dfs.iter(root);
+        fn trigger_in_order(
+            root: Entity,
+            mut commands: Commands,
+            orders: &Query<&ScoringOrder>,
+            per_target: &Query<(), With<Targets>>,
+            dfs: &mut DFSPostTraversal<(With<Score>, Without<Targets>)>,
+        ) {
+            let mut trigger = |entity: Entity| {
+                if per_target.contains(entity) {
+                    // Score its whole tree once per target
+                    commands.queue(move |world: &mut World| Targets::score(world, entity));
+                } else {
+                    commands.trigger_targets(OnScore, entity);
+                    commands.trigger_targets(OnScorePostProcess, entity);
+                }
+            };
 
-            for entity in sorted {
-                commands.trigger_targets(OnScore, entity);
+            if let Ok(order) = orders.get(root) {
+                for &entity in order.entities() {
+                    trigger(entity);
+                }
+            } else {
+                let order: Vec<Entity> = dfs.iter(root).collect();
+                for &entity in &order {
+                    trigger(entity);
+                }
+                commands.entity(root).try_insert(ScoringOrder(order));
             }
         }
 
         if let Some(targeted_root) = trigger.get_entity() {
             // Do scoring for the given entity
-            trigger_in_order(targeted_root, commands.reborrow(), &mut dfs);
+            trigger_in_order(targeted_root, commands.reborrow(), &orders, &per_target, &mut dfs);
         } else {
             // Do scoring globally
-            // Find all score entities that have no parents at all, or whose parents are not score entities
-            let roots = scoreable_roots.iter().filter_map(|(entity, parent)| {
-                if let Some(parent) = parent {
-                    if root_parents.contains(parent.0) {
-                        Some(entity)
-                    } else {
-                        None
-                    }
-                } else {
-                    Some(entity)
-                }
-            });
-            for root in roots {
-                trigger_in_order(root, commands.reborrow(), &mut dfs);
+            for root in roots.iter() {
+                trigger_in_order(root, commands.reborrow(), &orders, &per_target, &mut dfs);
             }
         }
     }
+
+    /// Same as [`ScoringPlugin::run_scoring_post_order_dfs`], but skips [`Score`] entities that are clean.
+    ///
+    /// See [`ScoringPlugin::incremental`] for more information.
+    pub fn run_scoring_incremental(trigger: Trigger<RunScoring>, mut commands: Commands, roots: ScoreRoots) {
+        let roots: Vec<Entity> = if let Some(targeted_root) = trigger.get_entity() {
+            vec![targeted_root]
+        } else {
+            roots.iter().collect()
+        };
+
+        commands.queue(move |world: &mut World| IncrementalScorers::score(world, &roots));
+    }
+
+    /// Same as [`ScoringPlugin::run_scoring_post_order_dfs`], but scores independent roots in parallel.
+    ///
+    /// See [`ScoringPlugin::parallel`] for more information.
+    pub fn run_scoring_parallel(trigger: Trigger<RunScoring>, mut commands: Commands, roots: ScoreRoots) {
+        let roots: Vec<Entity> = if let Some(targeted_root) = trigger.get_entity() {
+            vec![targeted_root]
+        } else {
+            roots.iter().collect()
+        };
+
+        commands.queue(move |world: &mut World| ParallelScorers::score(world, &roots));
+    }
+
+    /// [`Observer`] that removes the [`ScoringOrder`] of all ancestors of an entity whose parent is set or removed,
+    /// for the [`ChildOf`] or [`ScoreOf`] [`Relationship`] `R`.
+    pub fn invalidate_scoring_order_on_parent_change<E: Event, R: Relationship>(
+        trigger: Trigger<E, R>,
+        mut world: DeferredWorld,
+    ) {
+        if let Some(parent) = world.get::<R>(trigger.target()).map(Relationship::get) {
+            ScoringOrder::invalidate(&mut world, parent);
+        }
+    }
+}
+
+/// Returns the children of the given entity if it is a [`Score`] entity, which are the ones traversed when scoring.
+///
+/// The children of [`Targets`] entities are not traversed, as they are scored by [`Targets::score`] instead.
+fn score_children(world: &World, entity: Entity) -> &[Entity] {
+    world
+        .get_entity(entity)
+        .ok()
+        .filter(|entity_ref| entity_ref.contains::<Score>() && !entity_ref.contains::<Targets>())
+        .map_or(&[], |entity_ref| ScoreHierarchy::of(world).children(&entity_ref))
+}
+
+/// Returns the depth-first post-order of the given root, only descending into [`Score`] entities.
+///
+/// Matches the order of [`ScoringPlugin::run_scoring_post_order_dfs`].
+fn post_order(world: &World, root: Entity) -> Vec<Entity> {
+    let mut order = Vec::new();
+    let mut stack = vec![(root, score_children(world, root), 0)];
+
+    while let Some((entity, children, next_child)) = stack.last_mut() {
+        if let Some(&child) = children.get(*next_child) {
+            *next_child += 1;
+            stack.push((child, score_children(world, child), 0));
+        } else {
+            order.push(*entity);
+            stack.pop();
+        }
+    }
+
+    order
+}
+
+/// Triggers [`OnScore`] and then [`OnScorePostProcess`] for each entity in the given order, one entity at a time.
+///
+/// [`Targets`] entities are scored with [`Targets::score`] instead.
+fn score_in_order(world: &mut World, order: &[Entity]) {
+    for &entity in order {
+        if world.get::<Targets>(entity).is_some() {
+            Targets::score(world, entity);
+        } else {
+            world.trigger_targets(OnScore, entity);
+            world.trigger_targets(OnScorePostProcess, entity);
+            world.flush();
+        }
+    }
+}
+
+/// Serializes the reflected trait object held by [`Evaluated`], [`PostEvaluated`] or [`Measured`]
+/// along with its type path, or fails if its type isn't registered with the trait's type data.
+fn serialize_registered<S: Serializer>(
+    value: Option<&dyn Reflect>,
+    trait_name: &str,
+    serializer: S,
+    registry: &TypeRegistry,
+) -> Result<S::Ok, S::Error> {
+    let Some(value) = value else {
+        return Err(S::Error::custom(format_args!(
+            "the value's type is not registered with `#[reflect({trait_name})]`"
+        )));
+    };
+    ReflectSerializer::new(value.as_partial_reflect(), registry).serialize(serializer)
+}
+
+/// Deserializes a value serialized by [`serialize_registered`], returning it along with its type registration.
+fn deserialize_registered<'de, 'r, D: Deserializer<'de>>(
+    deserializer: D,
+    registry: &'r TypeRegistry,
+) -> Result<(Box<dyn Reflect>, &'r TypeRegistration), D::Error> {
+    let value = ReflectDeserializer::new(registry).deserialize(deserializer)?;
+    let Some(registration) = value
+        .get_represented_type_info()
+        .and_then(|info| registry.get(info.type_id()))
+    else {
+        return Err(D::Error::custom(
+            "the value's type is not registered in the type registry",
+        ));
+    };
+    let value = registration
+        .data::<ReflectFromReflect>()
+        .and_then(|from_reflect| from_reflect.from_reflect(value.as_partial_reflect()))
+        .ok_or_else(|| {
+            D::Error::custom(format_args!(
+                "`{}` can't be created from reflection, is it registered with `#[reflect(FromReflect)]`?",
+                registration.type_info().type_path()
+            ))
+        })?;
+    Ok((value, registration))
+}
+
+/// [`Component`] caching the depth-first post-order traversal of the scoring tree rooted at this entity,
+/// so that scoring an unchanged tree is just a walk over a list of entities.
+///
+/// This is inserted by [`ScoringPlugin::run_scoring_post_order_dfs`] or [`IncrementalScorers::score`] when the entity
+/// is first scored as a root, and removed whenever an entity in its tree gains or loses a parent or a [`Score`].
+#[derive(Component, Reflect)]
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+#[reflect(Component, PartialEq, Debug, Default)]
+pub struct ScoringOrder(Vec<Entity>);
+
+impl ScoringOrder {
+    /// Returns the cached entities, children before their parents.
+    #[must_use]
+    pub fn entities(&self) -> &[Entity] {
+        &self.0
+    }
+
+    /// Removes the [`ScoringOrder`] of the given entity and all of its ancestors.
+    pub fn invalidate(world: &mut DeferredWorld, start: Entity) {
+        let hierarchy = ScoreHierarchy::of(world);
+        let mut current = Some(start);
+        while let Some(entity) = current {
+            let Ok(entity_ref) = world.get_entity(entity) else {
+                // Already despawned, e.g. while despawning a whole tree
+                break;
+            };
+            let cached = entity_ref.contains::<ScoringOrder>();
+            current = hierarchy.parent(&entity_ref);
+            if cached {
+                world.commands().entity(entity).try_remove::<ScoringOrder>();
+            }
+        }
+    }
+
+    /// [`Score`] hook that invalidates the [`ScoringOrder`] of the entity and all of its ancestors,
+    /// as gaining or losing a [`Score`] changes which children are traversed.
+    fn on_score_changed(mut world: DeferredWorld, ctx: HookContext) {
+        Self::invalidate(&mut world, ctx.entity);
+    }
 }
 
 /// [`Component`] for an entity's score for a given score type, ranging from 0 to 1.
 #[derive(Component, Reflect)]
 #[derive(Clone, Copy, PartialEq, PartialOrd, Debug, Default)]
 #[reflect(Component, PartialEq, Debug, Default)]
+#[component(on_add = ScoringOrder::on_score_changed, on_remove = ScoringOrder::on_score_changed)]
 pub struct Score {
     /// The score value, clamped to the range `[0, 1]`.
     value: f32,
@@ -385,25 +701,43 @@ pub fn score_ancestor<T: Component, ScoreMarker: Component>(
 
 #[cfg(test)]
 mod tests {
+    use std::{any::TypeId, sync::Arc, time::Duration};
+
     use approx::assert_relative_eq;
     use bevy::{
         app::App,
-        ecs::observer::ObserverState,
-        prelude::{With, World},
+        ecs::{entity::EntityHashMap, observer::ObserverState},
+        prelude::{
+            AppTypeRegistry, ChildOf, Component, Entity, FromReflect, Query, Reflect, ResMut, Resource, Time, Trigger,
+            TypePath, Vec2, With, World,
+        },
+        reflect::{
+            Struct, TypeRegistry,
+            serde::{TypedReflectDeserializer, TypedReflectSerializer},
+        },
+        scene::{DynamicSceneBuilder, serde::SceneDeserializer},
     };
+    use serde::de::DeserializeSeed;
 
     use crate::{
-        event::RunScoring,
+        TurnBasedLifecyclePlugin, TurnQueue,
+        acting::ActionPlugin,
+        ecs::TargetedAction,
+        event::{AdvanceTurn, OnActionEnded, OnScore, OnScorePostProcess, RunScoring},
+        picking::{Picker, PickingPlugin},
         scoring::{
-            AllOrNothing, Evaluated, FixedScore, Measured, PowerEvaluator, Product, Score, ScoringPlugin, Sum,
-            Weighted, WeightedMax, WeightedProduct, WeightedRMS, WeightedSum, Winning,
+            AllOrNothing, ChildAggregateScorer, Cooldown, CubicKeyframe, CubicSplineEvaluator, Evaluated, Evaluator,
+            FixedScore, LastScored, LinearEvaluator, Measured, PiecewiseLinearEvaluator, PostEvaluated, PowerEvaluator,
+            Product, Score, ScoreHierarchy, ScoreHistory, ScoreOf, ScoreTimestamp, Scorer, Scorers, ScoringOrder,
+            ScoringPlugin, SkipScoringEvents, Sum, Targets, Volatile, Weighted, WeightedMax, WeightedProduct,
+            WeightedRMS, WeightedSum, Winning, score_ancestor, score_target,
         },
     };
 
     #[test]
     fn all_or_nothing() {
         let mut app = App::new();
-        app.add_plugins(ScoringPlugin);
+        app.add_plugins(ScoringPlugin::default());
 
         let world = app.world_mut();
 
@@ -423,13 +757,13 @@ mod tests {
             world.get::<Score>(parent).unwrap().get(),
             "Parent score should be 1.0."
         );
-        assert_eq!(3, count_observers(world));
+        assert_eq!(7, count_observers(world));
     }
 
     #[test]
     fn evaluated_power() {
         let mut app = App::new();
-        app.add_plugins(ScoringPlugin);
+        app.add_plugins(ScoringPlugin::default());
 
         let world = app.world_mut();
 
@@ -446,10 +780,68 @@ mod tests {
         assert_relative_eq!(0.49, world.get::<Score>(entity).unwrap().get());
     }
 
+    #[test]
+    fn evaluated_piecewise_linear() {
+        let mut app = App::new();
+        app.add_plugins(ScoringPlugin::default());
+
+        let world = app.world_mut();
+
+        let evaluator = PiecewiseLinearEvaluator::new([Vec2::new(1., 0.), Vec2::new(0., 1.), Vec2::new(0.5, 0.2)]);
+        let entity = world
+            .spawn((Score::default(), Evaluated::new(evaluator)))
+            .with_children(|parent| {
+                parent.spawn((Score::default(), FixedScore::new(0.75)));
+            })
+            .id();
+
+        world.trigger_targets(RunScoring, entity);
+        world.flush();
+
+        assert_relative_eq!(0.1, world.get::<Score>(entity).unwrap().get());
+    }
+
+    #[test]
+    fn evaluated_cubic_spline() {
+        let mut app = App::new();
+        app.add_plugins(ScoringPlugin::default());
+
+        let world = app.world_mut();
+
+        let evaluator = CubicSplineEvaluator::new([
+            CubicKeyframe::new(Vec2::new(0., 0.), 0.),
+            CubicKeyframe::new(Vec2::new(1., 1.), 0.),
+        ]);
+        let entity = world
+            .spawn((Score::default(), Evaluated::new(evaluator)))
+            .with_children(|parent| {
+                parent.spawn((Score::default(), FixedScore::new(0.25)));
+            })
+            .id();
+
+        world.trigger_targets(RunScoring, entity);
+        world.flush();
+
+        // Smoothstep: 3t^2 - 2t^3
+        assert_relative_eq!(0.15625, world.get::<Score>(entity).unwrap().get());
+    }
+
+    #[test]
+    fn evaluated_keyframes_nan() {
+        let piecewise = PiecewiseLinearEvaluator::new([Vec2::new(0., 0.2), Vec2::new(0.5, 0.6), Vec2::new(1., 1.)]);
+        assert_relative_eq!(0.2, piecewise.evaluate(f32::NAN));
+
+        let cubic = CubicSplineEvaluator::new([
+            CubicKeyframe::new(Vec2::new(0., 0.2), 0.),
+            CubicKeyframe::new(Vec2::new(1., 1.), 0.),
+        ]);
+        assert_relative_eq!(0.2, cubic.evaluate(f32::NAN));
+    }
+
     #[test]
     fn fixed() {
         let mut app = App::new();
-        app.add_plugins(ScoringPlugin);
+        app.add_plugins(ScoringPlugin::default());
 
         let world = app.world_mut();
 
@@ -459,13 +851,13 @@ mod tests {
         world.flush();
 
         assert_eq!(0.5, world.get::<Score>(entity).unwrap().get(), "Score should be 0.5.");
-        assert_eq!(2, count_observers(world));
+        assert_eq!(6, count_observers(world));
     }
 
     #[test]
     fn measured_weighted_sum() {
         let mut app = App::new();
-        app.add_plugins(ScoringPlugin);
+        app.add_plugins(ScoringPlugin::default());
 
         let world = app.world_mut();
 
@@ -481,13 +873,13 @@ mod tests {
         world.flush();
 
         assert_relative_eq!(0.89, world.get::<Score>(parent).unwrap().get());
-        assert_eq!(3, count_observers(world));
+        assert_eq!(7, count_observers(world));
     }
 
     #[test]
     fn measured_weighted_product() {
         let mut app = App::new();
-        app.add_plugins(ScoringPlugin);
+        app.add_plugins(ScoringPlugin::default());
 
         let world = app.world_mut();
 
@@ -503,13 +895,13 @@ mod tests {
         world.flush();
 
         assert_relative_eq!(0.0648, world.get::<Score>(parent).unwrap().get());
-        assert_eq!(3, count_observers(world));
+        assert_eq!(7, count_observers(world));
     }
 
     #[test]
     fn measured_weighted_max() {
         let mut app = App::new();
-        app.add_plugins(ScoringPlugin);
+        app.add_plugins(ScoringPlugin::default());
 
         let world = app.world_mut();
 
@@ -525,13 +917,13 @@ mod tests {
         world.flush();
 
         assert_relative_eq!(0.81, world.get::<Score>(parent).unwrap().get());
-        assert_eq!(3, count_observers(world));
+        assert_eq!(7, count_observers(world));
     }
 
     #[test]
     fn measured_weighted_rms() {
         let mut app = App::new();
-        app.add_plugins(ScoringPlugin);
+        app.add_plugins(ScoringPlugin::default());
 
         let world = app.world_mut();
 
@@ -547,13 +939,199 @@ mod tests {
         world.flush();
 
         assert_relative_eq!(0.8905055, world.get::<Score>(parent).unwrap().get());
-        assert_eq!(3, count_observers(world));
+        assert_eq!(7, count_observers(world));
+    }
+
+    #[test]
+    fn post_evaluated_measured() {
+        let mut app = App::new();
+        app.add_plugins(ScoringPlugin::default());
+
+        let world = app.world_mut();
+
+        let grandparent = world
+            .spawn((Score::default(), Sum::new(0.)))
+            .with_children(|grandparent| {
+                grandparent
+                    .spawn((
+                        Score::default(),
+                        Measured::new(WeightedSum),
+                        PostEvaluated::new(PowerEvaluator::default()),
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn((Score::default(), FixedScore::new(0.9), Weighted::new(0.5)));
+                        parent.spawn((Score::default(), FixedScore::new(0.7), Weighted::new(0.5)));
+                    });
+            })
+            .id();
+
+        world.trigger_targets(RunScoring, grandparent);
+        world.flush();
+
+        // The parent is curved in place before the grandparent sums it.
+        assert_relative_eq!(0.64, world.get::<Score>(grandparent).unwrap().get());
+        assert_eq!(9, count_observers(world));
+    }
+
+    #[test]
+    fn reflect_serialize() {
+        let mut app = App::new();
+        app.add_plugins(ScoringPlugin::default());
+
+        let registry = app.world().resource::<AppTypeRegistry>().read();
+
+        fn round_trip<T: Reflect + FromReflect + TypePath>(value: &T, registry: &TypeRegistry) -> T {
+            let serialized = ron::to_string(&TypedReflectSerializer::new(value, registry)).unwrap();
+            let registration = registry.get(TypeId::of::<T>()).unwrap();
+            let mut deserializer = ron::Deserializer::from_str(&serialized).unwrap();
+            let deserialized = TypedReflectDeserializer::new(registration, registry)
+                .deserialize(&mut deserializer)
+                .unwrap();
+            T::from_reflect(deserialized.as_partial_reflect()).unwrap()
+        }
+
+        let evaluated = round_trip(
+            &Evaluated::new(PowerEvaluator::new(3., Vec2::ZERO, Vec2::ONE)),
+            &registry,
+        );
+        assert_relative_eq!(0.125, evaluated.evaluate(0.5));
+
+        let post_evaluated = round_trip(
+            &PostEvaluated::new(PowerEvaluator::new(2., Vec2::ZERO, Vec2::ONE)),
+            &registry,
+        );
+        assert_relative_eq!(0.25, post_evaluated.evaluate(0.5));
+
+        let measured = round_trip(&Measured::new(WeightedMax), &registry);
+        let score = measured.calculate(vec![
+            (&Score::new(0.5), &Weighted::new(0.5)),
+            (&Score::new(0.8), &Weighted::new(1.)),
+        ]);
+        assert_relative_eq!(0.8, score.get());
+
+        // Closures aren't registered, so they can't be serialized.
+        let closure = Evaluated::new(|value: f32| value);
+        assert!(ron::to_string(&TypedReflectSerializer::new(&closure, &registry)).is_err());
+    }
+
+    #[test]
+    fn reflect_scene() {
+        let mut app = App::new();
+        app.add_plugins(ScoringPlugin::default());
+        let world = app.world_mut();
+
+        let root = world
+            .spawn((
+                Measured::new(WeightedMax),
+                PostEvaluated::new(PowerEvaluator::new(2., Vec2::ZERO, Vec2::ONE)),
+                Score::default(),
+            ))
+            .id();
+        let evaluated = world
+            .spawn((
+                Evaluated::new(PowerEvaluator::new(3., Vec2::ZERO, Vec2::ONE)),
+                Score::default(),
+                ScoreOf(root),
+            ))
+            .id();
+        let leaf = world
+            .spawn((FixedScore::new(0.5), Score::default(), ScoreOf(evaluated)))
+            .id();
+
+        let scene = DynamicSceneBuilder::from_world(world)
+            .extract_entities([root, evaluated, leaf].into_iter())
+            .build();
+        let serialized = scene.serialize(&world.resource::<AppTypeRegistry>().read()).unwrap();
+
+        let mut other = App::new();
+        other.add_plugins(ScoringPlugin::default());
+        let world = other.world_mut();
+        let registry = world.resource::<AppTypeRegistry>().clone();
+        let mut deserializer = ron::Deserializer::from_str(&serialized).unwrap();
+        let scene = SceneDeserializer {
+            type_registry: &registry.read(),
+        }
+        .deserialize(&mut deserializer)
+        .unwrap();
+        let mut entity_map = EntityHashMap::default();
+        scene.write_to_world(world, &mut entity_map).unwrap();
+
+        let root = entity_map[&root];
+        world.trigger_targets(RunScoring, root);
+        world.flush();
+        // max(0.5^3) = 0.125, then 0.125^2
+        assert_relative_eq!(0.015625, world.get::<Score>(root).unwrap().get());
+
+        // The evaluator can be replaced through reflection
+        let replacement: Arc<dyn Evaluator> = Arc::new(LinearEvaluator::from_range(0., 2.));
+        let mut evaluated = world.get_mut::<Evaluated>(entity_map[&evaluated]).unwrap();
+        evaluated.field_mut("evaluator").unwrap().apply(&replacement);
+        assert_relative_eq!(0.5, evaluated.evaluate(1.));
+    }
+
+    #[test]
+    fn derive_scorer() {
+        // Each instantiation of a generic scorer spawns its own observer.
+        #[derive(Scorer)]
+        #[scorer(aggregate)]
+        struct Nth<const N: usize>;
+
+        impl<const N: usize> ChildAggregateScorer for Nth<N> {
+            fn aggregate(&self, mut children: impl Iterator<Item = Score>) -> Score {
+                children.nth(N).unwrap_or_default()
+            }
+        }
+
+        #[derive(Scorer)]
+        #[scorer(observer = Self::score, observer = Self::post_process)]
+        struct Halved;
+
+        impl Halved {
+            fn score(trigger: Trigger<OnScore>, mut scores: Query<&mut Score, With<Halved>>) {
+                if let Ok(mut score) = scores.get_mut(trigger.target()) {
+                    score.set(1.);
+                }
+            }
+
+            fn post_process(trigger: Trigger<OnScorePostProcess>, mut scores: Query<&mut Score, With<Halved>>) {
+                if let Ok(mut score) = scores.get_mut(trigger.target()) {
+                    let halved = score.get() / 2.;
+                    score.set(halved);
+                }
+            }
+        }
+
+        let mut app = App::new();
+        app.add_plugins(ScoringPlugin::default());
+
+        let world = app.world_mut();
+
+        fn spawn(world: &mut World, scorer: impl Component) -> Entity {
+            world
+                .spawn((Score::default(), scorer))
+                .with_children(|parent| {
+                    parent.spawn((Score::default(), FixedScore::new(0.2)));
+                    parent.spawn((Score::default(), Halved));
+                })
+                .id()
+        }
+        let first = spawn(world, Nth::<0>);
+        let second = spawn(world, Nth::<1>);
+        world.flush();
+
+        world.trigger(RunScoring);
+        world.flush();
+
+        assert_relative_eq!(0.2, world.get::<Score>(first).unwrap().get());
+        assert_relative_eq!(0.5, world.get::<Score>(second).unwrap().get());
+        // The plugin's 5 observers, plus FixedScore's, Nth<0>'s, Nth<1>'s, and Halved's 2 observers.
+        assert_eq!(10, count_observers(world));
     }
 
     #[test]
     fn product() {
         let mut app = App::new();
-        app.add_plugins(ScoringPlugin);
+        app.add_plugins(ScoringPlugin::default());
 
         let world = app.world_mut();
 
@@ -569,13 +1147,13 @@ mod tests {
         world.flush();
 
         assert_relative_eq!(0.72, world.get::<Score>(parent).unwrap().get(),);
-        assert_eq!(3, count_observers(world));
+        assert_eq!(7, count_observers(world));
     }
 
     #[test]
     fn sum() {
         let mut app = App::new();
-        app.add_plugins(ScoringPlugin);
+        app.add_plugins(ScoringPlugin::default());
 
         let world = app.world_mut();
 
@@ -595,13 +1173,13 @@ mod tests {
             world.get::<Score>(parent).unwrap().get(),
             "Parent score should be 1.0."
         );
-        assert_eq!(3, count_observers(world));
+        assert_eq!(7, count_observers(world));
     }
 
     #[test]
     fn winning() {
         let mut app = App::new();
-        app.add_plugins(ScoringPlugin);
+        app.add_plugins(ScoringPlugin::default());
 
         let world = app.world_mut();
 
@@ -621,7 +1199,756 @@ mod tests {
             world.get::<Score>(parent).unwrap().get(),
             "Parent score should be 0.9."
         );
-        assert_eq!(3, count_observers(world));
+        assert_eq!(7, count_observers(world));
+    }
+
+    #[test]
+    fn scoring_order_cache() {
+        let mut app = App::new();
+        app.add_plugins(ScoringPlugin::default());
+
+        let world = app.world_mut();
+
+        let mut children = Vec::new();
+        let parent = world
+            .spawn((Score::default(), Sum::new(0.)))
+            .with_children(|parent| {
+                children.push(parent.spawn((Score::default(), FixedScore::new(0.2))).id());
+                children.push(parent.spawn((Score::default(), FixedScore::new(0.3))).id());
+            })
+            .id();
+
+        world.trigger_targets(RunScoring, parent);
+        world.flush();
+
+        assert_relative_eq!(0.5, world.get::<Score>(parent).unwrap().get());
+        assert_eq!(
+            &[children[0], children[1], parent],
+            world.get::<ScoringOrder>(parent).unwrap().entities()
+        );
+
+        // Adding a child invalidates the cache
+        let added = world
+            .spawn((Score::default(), FixedScore::new(0.4), ChildOf(parent)))
+            .id();
+        world.flush();
+        assert!(world.get::<ScoringOrder>(parent).is_none());
+
+        world.trigger_targets(RunScoring, parent);
+        world.flush();
+
+        assert_relative_eq!(0.9, world.get::<Score>(parent).unwrap().get());
+        assert_eq!(
+            &[children[0], children[1], added, parent],
+            world.get::<ScoringOrder>(parent).unwrap().entities()
+        );
+
+        // Removing a child invalidates the cache
+        world.entity_mut(children[0]).despawn();
+        world.flush();
+        assert!(world.get::<ScoringOrder>(parent).is_none());
+
+        world.trigger_targets(RunScoring, parent);
+        world.flush();
+
+        assert_relative_eq!(0.7, world.get::<Score>(parent).unwrap().get());
+
+        // Despawning the whole tree doesn't trip over the already despawned ancestors
+        world.entity_mut(parent).despawn();
+        world.flush();
+        assert!(world.get_entity(parent).is_err());
+    }
+
+    #[test]
+    fn score_of() {
+        #[derive(Component)]
+        struct Thirst(f32);
+
+        impl From<&Thirst> for Score {
+            fn from(thirst: &Thirst) -> Self {
+                Score::new(thirst.0)
+            }
+        }
+
+        #[derive(Component)]
+        struct Thirsty;
+
+        let mut app = App::new();
+        app.add_plugins(ScoringPlugin::default())
+            .add_observer(score_ancestor::<Thirst, Thirsty>);
+
+        let world = app.world_mut();
+
+        let actor = world.spawn(Thirst(0.3)).id();
+        let parent = world.spawn((Score::default(), Sum::new(0.), ScoreOf(actor))).id();
+        let fixed = world
+            .spawn((Score::default(), FixedScore::new(0.2), ScoreOf(parent)))
+            .id();
+        // Still migrating from `ChildOf`, which is followed by entities without `Scorers`
+        let nested = world.spawn((Score::default(), Sum::new(0.), ScoreOf(parent))).id();
+        let thirsty = world.spawn((Score::default(), Thirsty, ChildOf(nested))).id();
+        // Ignored, as the parent has `Scorers`
+        let ignored = world
+            .spawn((Score::default(), FixedScore::new(0.4), ChildOf(parent)))
+            .id();
+
+        world.trigger(RunScoring);
+        world.flush();
+
+        assert_relative_eq!(0.3, world.get::<Score>(thirsty).unwrap().get());
+        assert_relative_eq!(0.5, world.get::<Score>(parent).unwrap().get());
+        assert_eq!(
+            &[fixed, thirsty, nested, parent],
+            world.get::<ScoringOrder>(parent).unwrap().entities()
+        );
+        assert_eq!(0.0, world.get::<Score>(ignored).unwrap().get());
+
+        // Changing a `ScoreOf` invalidates the cache
+        world.entity_mut(fixed).insert(ScoreOf(nested));
+        world.flush();
+        assert!(world.get::<ScoringOrder>(parent).is_none());
+        assert_eq!(&[nested], &world.get::<Scorers>(parent).unwrap()[..]);
+
+        world.trigger(RunScoring);
+        world.flush();
+
+        // `nested` now only sums `fixed`, having its own `Scorers`
+        assert_relative_eq!(0.2, world.get::<Score>(parent).unwrap().get());
+
+        // Despawning the parent despawns its `Scorers`
+        world.entity_mut(parent).despawn();
+        assert!(world.get_entity(fixed).is_err());
+        assert!(world.get_entity(nested).is_err());
+    }
+
+    #[test]
+    fn score_of_only() {
+        let mut app = App::new();
+        app.add_plugins(ScoringPlugin {
+            hierarchy: ScoreHierarchy::Scorers,
+            ..Default::default()
+        });
+
+        let world = app.world_mut();
+
+        let parent = world.spawn((Score::default(), Sum::new(0.))).id();
+        world.spawn((Score::default(), FixedScore::new(0.2), ScoreOf(parent)));
+        // Not part of the scoring tree, so it's scored as its own root
+        let child = world
+            .spawn((Score::default(), FixedScore::new(0.4), ChildOf(parent)))
+            .id();
+
+        world.trigger(RunScoring);
+        world.flush();
+
+        assert_relative_eq!(0.2, world.get::<Score>(parent).unwrap().get());
+        assert_relative_eq!(0.4, world.get::<Score>(child).unwrap().get());
+    }
+
+    #[test]
+    fn history_turns_incremental() {
+        let mut app = App::new();
+        app.add_plugins((
+            ScoringPlugin {
+                incremental: true,
+                ..Default::default()
+            },
+            PickingPlugin,
+            ActionPlugin,
+            TurnBasedLifecyclePlugin::default(),
+        ));
+        let world = app.world_mut();
+
+        let thirst = world
+            .spawn((Score::default(), FixedScore::new(0.8), ScoreHistory::turns(10)))
+            .id();
+        let actor = world.spawn_empty().add_child(thirst).id();
+        world.resource_mut::<TurnQueue>().push(actor);
+        world.flush();
+
+        // Unchanged scores are still recorded on every turn
+        for _ in 0..3 {
+            world.trigger(AdvanceTurn);
+            world.flush();
+        }
+        // Scoring again during a turn replaces its sample
+        world.trigger(RunScoring);
+        world.flush();
+
+        let timestamps: Vec<ScoreTimestamp> = world
+            .get::<ScoreHistory>(thirst)
+            .unwrap()
+            .samples()
+            .map(|(timestamp, _)| timestamp)
+            .collect();
+        assert_eq!(
+            vec![
+                ScoreTimestamp::Turn(1),
+                ScoreTimestamp::Turn(2),
+                ScoreTimestamp::Turn(3)
+            ],
+            timestamps
+        );
+    }
+
+    #[test]
+    fn incremental() {
+        #[derive(Resource, Default)]
+        struct Scored(Vec<Entity>);
+
+        let mut app = App::new();
+        app.add_plugins(ScoringPlugin {
+            incremental: true,
+            ..Default::default()
+        })
+        .init_resource::<Scored>()
+        .add_observer(|trigger: Trigger<OnScore>, mut scored: ResMut<Scored>| {
+            scored.0.push(trigger.target());
+        });
+
+        let world = app.world_mut();
+
+        let root = world.spawn((Score::default(), Sum::new(0.))).id();
+        let nested = world.spawn((Score::default(), Sum::new(0.), ChildOf(root))).id();
+        let fixed = world
+            .spawn((Score::default(), FixedScore::new(0.2), ChildOf(nested)))
+            .id();
+        let weighted = world
+            .spawn((
+                Score::default(),
+                FixedScore::new(0.3),
+                Weighted::new(1.),
+                ChildOf(nested),
+            ))
+            .id();
+        let volatile = world
+            .spawn((Score::default(), FixedScore::new(0.1), Volatile, ChildOf(root)))
+            .id();
+
+        let run = |world: &mut World| {
+            world.resource_mut::<Scored>().0.clear();
+            world.trigger(RunScoring);
+            world.flush();
+            std::mem::take(&mut world.resource_mut::<Scored>().0)
+        };
+
+        // Everything is scored the first time
+        assert_eq!(vec![fixed, weighted, nested, volatile, root], run(world));
+        assert_relative_eq!(0.6, world.get::<Score>(root).unwrap().get());
+        assert!(world.get::<LastScored>(root).is_some());
+
+        // Only volatile entities are scored when nothing changed, and their unchanged scores don't dirty the root
+        assert_eq!(vec![volatile], run(world));
+
+        // Changing a scorer re-scores its ancestors
+        world.get_mut::<FixedScore>(fixed).unwrap().set_value(0.4);
+        assert_eq!(vec![fixed, nested, volatile, root], run(world));
+        assert_relative_eq!(0.8, world.get::<Score>(root).unwrap().get());
+
+        // Changing a scorer without changing its score stops at the scorer
+        world.get_mut::<FixedScore>(fixed).unwrap().set_value(0.4);
+        assert_eq!(vec![fixed, volatile], run(world));
+
+        // Changing a weight re-scores the parent, even though the child's score didn't change
+        world.get_mut::<Weighted>(weighted).unwrap().set(0.5);
+        assert_eq!(vec![weighted, nested, volatile], run(world));
+
+        // Setting a score between runs re-scores the parent, even though the child itself is clean
+        world.get_mut::<Score>(weighted).unwrap().set(0.9);
+        assert_eq!(vec![nested, volatile, root], run(world));
+
+        // Changing the tree's shape scores everything again
+        world.entity_mut(fixed).despawn();
+        world.flush();
+        assert_eq!(vec![weighted, nested, volatile, root], run(world));
+        assert_relative_eq!(0.4, world.get::<Score>(root).unwrap().get());
+    }
+
+    #[test]
+    fn parallel() {
+        #[derive(Component)]
+        struct Custom;
+
+        #[derive(Resource, Default)]
+        struct Scored(Vec<Entity>);
+
+        let mut app = App::new();
+        app.add_plugins(ScoringPlugin {
+            parallel: true,
+            ..Default::default()
+        })
+        .init_resource::<Scored>()
+        .add_observer(|trigger: Trigger<OnScore>, mut scored: ResMut<Scored>| {
+            scored.0.push(trigger.target());
+        });
+        app.add_observer(
+            |trigger: Trigger<OnScore>, mut scores: Query<&mut Score, With<Custom>>| {
+                if let Ok(mut score) = scores.get_mut(trigger.target()) {
+                    score.set(0.6);
+                }
+            },
+        );
+
+        let world = app.world_mut();
+
+        let parallel = world
+            .spawn((
+                Score::default(),
+                Sum::new(0.),
+                PostEvaluated::new(PowerEvaluator::default()),
+                SkipScoringEvents,
+            ))
+            .with_children(|parent| {
+                parent.spawn((Score::default(), FixedScore::new(0.3)));
+                parent
+                    .spawn((Score::default(), Product::new(0.)))
+                    .with_children(|parent| {
+                        parent.spawn((Score::default(), FixedScore::new(0.5)));
+                        parent.spawn((Score::default(), FixedScore::new(0.8)));
+                    });
+            })
+            .id();
+        // Not opted into skipping events, so it's scored with observers even though it could be scored in parallel
+        let unmarked = world.spawn((Score::default(), Sum::new(0.))).id();
+        let unmarked_child = world
+            .spawn((Score::default(), FixedScore::new(0.4), ChildOf(unmarked)))
+            .id();
+        let fallback = world
+            .spawn((Score::default(), Winning::new(0.)))
+            .with_children(|parent| {
+                parent.spawn((Score::default(), FixedScore::new(0.2)));
+                parent.spawn((Score::default(), Custom));
+            })
+            .id();
+        world.entity_mut(fallback).insert(SkipScoringEvents);
+
+        world.trigger(RunScoring);
+        world.flush();
+
+        assert_relative_eq!(0.49, world.get::<Score>(parallel).unwrap().get());
+        assert_relative_eq!(0.4, world.get::<Score>(unmarked).unwrap().get());
+        assert_relative_eq!(0.6, world.get::<Score>(fallback).unwrap().get());
+
+        let scored = &world.resource::<Scored>().0;
+        assert!(!scored.contains(&parallel));
+        assert!(scored.contains(&unmarked));
+        assert!(scored.contains(&unmarked_child));
+        assert!(scored.contains(&fallback));
+    }
+
+    #[test]
+    fn cooldown_time() {
+        #[derive(Component)]
+        struct Drink;
+
+        #[derive(Component)]
+        struct Idle;
+
+        for parallel in [false, true] {
+            let mut app = App::new();
+            app.add_plugins(ScoringPlugin {
+                parallel,
+                ..Default::default()
+            });
+            let world = app.world_mut();
+            world.init_resource::<Time>();
+
+            let drink = world.register_component::<Drink>();
+            let idle = world.register_component::<Idle>();
+
+            let thirst = world
+                .spawn((
+                    Score::default(),
+                    FixedScore::new(0.8),
+                    Cooldown::time(Duration::from_secs(2)),
+                    SkipScoringEvents,
+                ))
+                .id();
+            let actor = world
+                .spawn(Picker::new(idle).with(thirst, drink))
+                .add_child(thirst)
+                .id();
+            world.flush();
+
+            let score = |world: &mut World, advance: Duration| {
+                world.resource_mut::<Time>().advance_by(advance);
+                world.trigger(RunScoring);
+                world.flush();
+                world.get::<Score>(thirst).unwrap().get()
+            };
+
+            assert_relative_eq!(0.8, score(world, Duration::ZERO));
+
+            world.trigger_targets(OnActionEnded::completed(drink), TargetedAction(actor, drink));
+            world.flush();
+
+            assert_relative_eq!(0., score(world, Duration::ZERO));
+            assert_relative_eq!(0., score(world, Duration::from_secs(1)));
+            assert_relative_eq!(0.8, score(world, Duration::from_secs(1)));
+        }
+    }
+
+    #[test]
+    fn cooldown_turns() {
+        #[derive(Component)]
+        struct Drink;
+
+        #[derive(Component)]
+        struct Idle;
+
+        let mut app = App::new();
+        app.add_plugins(ScoringPlugin::default());
+        let world = app.world_mut();
+
+        let drink = world.register_component::<Drink>();
+        let idle = world.register_component::<Idle>();
+
+        let thirst = world
+            .spawn((Score::default(), FixedScore::new(0.8), Cooldown::turns(1)))
+            .id();
+        let actor = world
+            .spawn(Picker::new(idle).with(thirst, drink))
+            .add_child(thirst)
+            .id();
+        world.flush();
+
+        world.trigger_targets(OnActionEnded::completed(drink), TargetedAction(actor, drink));
+        world.flush();
+
+        world.trigger(RunScoring);
+        world.flush();
+        assert_relative_eq!(0., world.get::<Score>(thirst).unwrap().get());
+
+        world.trigger(RunScoring);
+        world.flush();
+        assert_relative_eq!(0.8, world.get::<Score>(thirst).unwrap().get());
+
+        // An inactive cooldown isn't marked as changed by scoring
+        let last_changed = world.entity(thirst).get_change_ticks::<Cooldown>().unwrap().changed;
+        world.increment_change_tick();
+        world.trigger(RunScoring);
+        world.flush();
+        assert_eq!(
+            last_changed,
+            world.entity(thirst).get_change_ticks::<Cooldown>().unwrap().changed
+        );
+    }
+
+    #[test]
+    fn targets_stateful() {
+        #[derive(Component)]
+        struct Health(f32);
+
+        impl From<&Health> for Score {
+            fn from(health: &Health) -> Self {
+                Score::new(1. - health.0 / 100.)
+            }
+        }
+
+        #[derive(Component)]
+        struct Weakest;
+
+        #[derive(Component)]
+        struct Attack;
+
+        #[derive(Component)]
+        struct Idle;
+
+        let mut app = App::new();
+        app.add_plugins(ScoringPlugin::default());
+        app.add_observer(score_target::<Health, Weakest>);
+        let world = app.world_mut();
+
+        let attack = world.register_component::<Attack>();
+        let idle = world.register_component::<Idle>();
+
+        let enemies = [
+            world.spawn(Health(80.)).id(),
+            world.spawn(Health(30.)).id(),
+            world.spawn(Health(50.)).id(),
+        ];
+        let weakest = world.spawn((Score::default(), Weakest, ScoreHistory::turns(10))).id();
+        let attacking = world
+            .spawn((
+                Score::default(),
+                Sum::new(0.),
+                Targets::new(enemies),
+                Cooldown::turns(2),
+                ScoreHistory::turns(10),
+            ))
+            .add_child(weakest)
+            .id();
+        let actor = world
+            .spawn(Picker::new(idle).with(attacking, attack))
+            .add_child(attacking)
+            .id();
+        world.flush();
+
+        world.trigger_targets(OnActionEnded::completed(attack), TargetedAction(actor, attack));
+        world.flush();
+
+        // The cooldown counts scoring runs, not candidates
+        for _ in 0..2 {
+            world.trigger(RunScoring);
+            world.flush();
+            assert_relative_eq!(0., world.get::<Score>(attacking).unwrap().get());
+        }
+        world.trigger(RunScoring);
+        world.flush();
+        assert_relative_eq!(0.7, world.get::<Score>(attacking).unwrap().get());
+        assert_eq!(Some(enemies[1]), world.get::<Targets>(attacking).unwrap().best());
+
+        // One sample per scoring run, for the best target
+        let samples = |entity| {
+            world
+                .get::<ScoreHistory>(entity)
+                .unwrap()
+                .samples()
+                .map(|(_, score)| score.get())
+                .collect::<Vec<_>>()
+        };
+        // While cooling down, all targets tie and the first one wins
+        let weakest_samples = samples(weakest);
+        assert_eq!(3, weakest_samples.len());
+        assert_relative_eq!(0.2, weakest_samples[0]);
+        assert_relative_eq!(0.2, weakest_samples[1]);
+        assert_relative_eq!(0.7, weakest_samples[2]);
+        assert_eq!(vec![0., 0., 0.7], samples(attacking));
+    }
+
+    #[test]
+    fn targets() {
+        #[derive(Component)]
+        struct Health(f32);
+
+        impl From<&Health> for Score {
+            fn from(health: &Health) -> Self {
+                Score::new(1. - health.0 / 100.)
+            }
+        }
+
+        #[derive(Component)]
+        struct Weakest;
+
+        #[derive(Component)]
+        struct Attack;
+
+        #[derive(Component)]
+        struct Idle;
+
+        for parallel in [false, true] {
+            let mut app = App::new();
+            app.add_plugins(ScoringPlugin {
+                parallel,
+                ..Default::default()
+            });
+            app.add_observer(score_target::<Health, Weakest>);
+            let world = app.world_mut();
+
+            let attack = world.register_component::<Attack>();
+            let idle = world.register_component::<Idle>();
+
+            let enemies = [
+                world.spawn(Health(80.)).id(),
+                world.spawn(Health(30.)).id(),
+                world.spawn(Health(30.)).id(),
+            ];
+            let weakest = world.spawn((Score::default(), Weakest)).id();
+            let eagerness = world.spawn((Score::default(), FixedScore::new(0.5))).id();
+            let attacking = world
+                .spawn((
+                    Score::default(),
+                    Product::new(0.),
+                    Targets::new(enemies),
+                    SkipScoringEvents,
+                ))
+                .add_children(&[weakest, eagerness])
+                .id();
+            let actor = world
+                .spawn(Picker::new(idle).with(attacking, attack))
+                .add_child(attacking)
+                .id();
+            world.flush();
+
+            world.trigger(RunScoring);
+            world.flush();
+
+            let targets = world.get::<Targets>(attacking).unwrap();
+            assert_eq!(None, targets.current());
+            // Ties are won by the first candidate
+            assert_eq!(Some(enemies[1]), targets.best());
+            let scores: Vec<_> = targets
+                .scores()
+                .iter()
+                .map(|(target, score)| (*target, score.get()))
+                .collect();
+            assert_eq!(3, scores.len());
+            for ((target, score), (enemy, expected)) in
+                scores.into_iter().zip(enemies.into_iter().zip([0.1, 0.35, 0.35]))
+            {
+                assert_eq!(enemy, target);
+                assert_relative_eq!(expected, score);
+            }
+            assert_relative_eq!(0.35, world.get::<Score>(attacking).unwrap().get());
+            assert_eq!(
+                Some(&enemies[1]),
+                world.get::<Picker>(actor).unwrap().best_targets.get(&attacking)
+            );
+
+            world.get_mut::<Targets>(attacking).unwrap().set_candidates([]);
+            world.trigger(RunScoring);
+            world.flush();
+
+            assert_eq!(None, world.get::<Targets>(attacking).unwrap().best());
+            assert_eq!(Score::MIN, *world.get::<Score>(attacking).unwrap());
+            assert!(world.get::<Picker>(actor).unwrap().best_targets.is_empty());
+        }
+    }
+
+    #[test]
+    fn history() {
+        for parallel in [false, true] {
+            let mut app = App::new();
+            app.add_plugins(ScoringPlugin {
+                parallel,
+                ..Default::default()
+            });
+            let world = app.world_mut();
+            world.init_resource::<Time>();
+
+            let fixed = world
+                .spawn((Score::default(), FixedScore::new(0.9), ScoreHistory::time(2)))
+                .id();
+            // Recorded after being curved in post-processing.
+            let curved = world
+                .spawn((
+                    Score::default(),
+                    Sum::new(0.),
+                    PostEvaluated::new(PowerEvaluator::new(2., Vec2::ZERO, Vec2::ONE)),
+                    ScoreHistory::time(2),
+                    SkipScoringEvents,
+                ))
+                .add_child(fixed)
+                .id();
+            let actor = world.spawn_empty().add_child(curved).id();
+            world.flush();
+
+            for seconds in [1, 2, 3] {
+                world.resource_mut::<Time>().advance_to(Duration::from_secs(seconds));
+                world.trigger(RunScoring);
+                world.flush();
+            }
+
+            let samples = |world: &World, entity| {
+                world
+                    .get::<ScoreHistory>(entity)
+                    .unwrap()
+                    .samples()
+                    .map(|(timestamp, score)| (timestamp, score.get()))
+                    .collect::<Vec<_>>()
+            };
+            let at = |seconds| ScoreTimestamp::Time(Duration::from_secs(seconds));
+            assert_eq!(vec![(at(2), 0.9), (at(3), 0.9)], samples(world, fixed));
+            let curved_samples = samples(world, curved);
+            assert_eq!(2, curved_samples.len());
+            for ((timestamp, score), seconds) in curved_samples.into_iter().zip([2, 3]) {
+                assert_eq!(at(seconds), timestamp);
+                assert_relative_eq!(0.81, score);
+            }
+
+            let csv = ScoreHistory::to_csv(world, actor);
+            let rows: Vec<&str> = csv.lines().collect();
+            assert_eq!("entity,timestamp,score", rows[0]);
+            assert_eq!(5, rows.len());
+            // Parents come before their children.
+            assert!(rows[1].starts_with(&format!("{curved},2,0.8")));
+            assert_eq!(format!("{fixed},3,0.9"), rows[4]);
+        }
+    }
+
+    #[cfg(feature = "trace")]
+    #[test]
+    fn trace() {
+        use crate::{
+            event::RunPicking,
+            picking::Highest,
+            scoring::{ScoringTrace, TraceNode},
+        };
+
+        #[derive(Component)]
+        struct Drink;
+
+        #[derive(Component)]
+        struct Idle;
+
+        let mut app = App::new();
+        app.add_plugins(crate::ObservedUtilityPlugins::TurnBased);
+        let world = app.world_mut();
+
+        let drink = world.register_component::<Drink>();
+        let idle = world.register_component::<Idle>();
+
+        let thirst = world
+            .spawn((Score::default(), FixedScore::new(0.9), Weighted::new(0.5)))
+            .id();
+        let hunger = world
+            .spawn((Score::default(), FixedScore::new(0.7), Weighted::new(0.5)))
+            .id();
+        let drinking = world
+            .spawn((Score::default(), Measured::new(WeightedSum)))
+            .add_children(&[thirst, hunger])
+            .id();
+        let actor = world
+            .spawn((
+                Picker::new(idle).with(drinking, drink),
+                Highest,
+                ScoringTrace::default(),
+            ))
+            .add_child(drinking)
+            .id();
+        world.flush();
+
+        assert_eq!(None, world.get::<ScoringTrace>(actor).unwrap().picked());
+
+        world.trigger(RunScoring);
+        world.flush();
+        world.trigger(RunPicking);
+        world.flush();
+
+        let trace = world.get::<ScoringTrace>(actor).unwrap();
+        let picked = trace.picked().unwrap();
+        assert!(picked.action.ends_with("Drink"));
+        assert_eq!(None, picked.target);
+        assert!(!picked.default);
+
+        let [root]: &[TraceNode; 1] = trace.nodes().try_into().unwrap();
+        assert_eq!(drinking, root.entity);
+        assert!(root.action.as_ref().is_some_and(|action| action.ends_with("Drink")));
+        assert_eq!(vec!["Measured".to_string()], root.kind);
+        assert_relative_eq!(0.8, root.score);
+        assert_eq!(vec![(0.9, 0.5), (0.7, 0.5)], root.inputs().collect::<Vec<_>>());
+        assert_eq!(
+            vec![thirst, hunger],
+            root.children.iter().map(|child| child.entity).collect::<Vec<_>>()
+        );
+        assert_eq!(vec!["FixedScore".to_string()], root.children[0].kind);
+        assert_eq!(None, root.children[0].action);
+
+        let printed = trace.to_string();
+        assert_eq!(4, printed.lines().count());
+        assert!(
+            printed
+                .lines()
+                .nth(1)
+                .unwrap()
+                .starts_with(&format!("  {drinking} Measured: 0.800"))
+        );
+        assert!(printed.lines().nth(2).unwrap().contains("(weight 0.500)"));
+
+        let json: serde_json::Value = serde_json::from_str(&trace.to_json()).unwrap();
+        assert_eq!("Measured", json["nodes"][0]["kind"][0]);
+        assert_relative_eq!(0.8, json["nodes"][0]["score"].as_f64().unwrap(), epsilon = 1e-6);
+        assert_eq!(false, json["picked"]["default"]);
     }
 
     fn count_observers(world: &mut World) -> usize {
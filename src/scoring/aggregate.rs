@@ -0,0 +1,84 @@
+use bevy::prelude::*;
+
+use crate::{
+    event::OnScore,
+    scoring::{Score, ScoreTree},
+};
+
+/// [`Score`] [`Component`] that scores by aggregating the [`Score`]s of its children, like [`Sum`] and [`Winning`] do.
+///
+/// Derive [`Scorer`] with `#[scorer(aggregate)]` to implement [`Component`] for it,
+/// spawning the [`score_children_aggregate`] [`Observer`] the first time it's added,
+/// and implementing [`ParallelScorer`] with [`ChildAggregateScorer::aggregate`].
+///
+/// Scorers that need more than their child scores can instead derive [`Scorer`] with `#[scorer(observer = ...)]`
+/// to spawn their own [`Observer`]s once.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_observed_utility::prelude::*;
+///
+/// # let mut app = App::new();
+/// # app.add_plugins(ObservedUtilityPlugins::TurnBased);
+/// # let mut world = app.world_mut();
+/// /// Scores the lowest child score.
+/// #[derive(Scorer)]
+/// #[scorer(aggregate)]
+/// struct Losing;
+///
+/// impl ChildAggregateScorer for Losing {
+///     fn aggregate(&self, children: impl Iterator<Item = Score>) -> Score {
+///         children.fold(Score::MAX, |min, score| if score < min { score } else { min })
+///     }
+/// }
+///
+/// let scorer = world
+///     .spawn((Losing, Score::default()))
+///     .with_related_entities::<ScoreOf>(|parent| {
+///         parent.spawn((FixedScore::new(0.7), Score::default()));
+///         parent.spawn((FixedScore::new(0.3), Score::default()));
+///     })
+///     .id();
+///
+/// world.trigger_targets(RunScoring, scorer);
+/// # world.flush();
+/// assert_eq!(0.3, world.get::<Score>(scorer).unwrap().get());
+/// ```
+///
+/// [`Sum`]: crate::scoring::Sum
+/// [`Winning`]: crate::scoring::Winning
+/// [`Scorer`]: crate::scoring::Scorer
+/// [`ParallelScorer`]: crate::scoring::ParallelScorer
+pub trait ChildAggregateScorer: Component {
+    /// Calculates the score from the [`Score`]s of the entity's children.
+    fn aggregate(&self, children: impl Iterator<Item = Score>) -> Score;
+}
+
+/// [`Observer`] for [`ChildAggregateScorer`] [`Score`] entities that scores based on all child [`Score`] entities.
+pub fn score_children_aggregate<T: ChildAggregateScorer>(
+    trigger: Trigger<OnScore>,
+    target: Query<&T>,
+    tree: ScoreTree,
+    mut scores: Query<&mut Score>,
+) {
+    let Ok(scorer) = target.get(trigger.target()) else {
+        // The entity is not scoring with this scorer.
+        return;
+    };
+    let children = tree.children(trigger.target());
+    if children.is_empty() {
+        // The entity has nothing to aggregate.
+        return;
+    }
+
+    let aggregate = scorer.aggregate(scores.iter_many(children).copied());
+
+    let Ok(mut actor_score) = scores.get_mut(trigger.target()) else {
+        // The entity is not scoring.
+        return;
+    };
+
+    *actor_score = aggregate;
+}
@@ -1,9 +1,6 @@
-use bevy::{
-    ecs::component::{ComponentHooks, Mutable, StorageType},
-    prelude::*,
-};
+use bevy::prelude::*;
 
-use crate::{ecs::CommandsExt, event::OnScore, scoring::Score};
+use crate::scoring::{ChildAggregateScorer, Score, Scorer};
 
 /// [`Score`] [`Component`] that scores all-or-nothing based on the sum of its child [`Score`] entities.
 ///
@@ -29,8 +26,9 @@ use crate::{ecs::CommandsExt, event::OnScore, scoring::Score};
 /// # world.flush();
 /// # assert_eq!(world.get::<Score>(scorer).unwrap().get(), 0.0);
 /// ```
-#[derive(Reflect, Clone, Copy, PartialEq, Debug, Default)]
+#[derive(Reflect, Scorer, Clone, Copy, PartialEq, Debug, Default)]
 #[reflect(Component, PartialEq, Debug, Default)]
+#[scorer(aggregate)]
 pub struct AllOrNothing {
     /// The threshold for the sum of child scores to be considered a success.
     threshold: Score,
@@ -56,45 +54,25 @@ impl AllOrNothing {
         self.threshold = threshold.into();
     }
 
-    /// [`Observer`] for [`AllOrNothing`] [`Score`] entities that scores based on all child [`Score`] entities.
-    fn observer(trigger: Trigger<OnScore>, target: Query<(&Children, &AllOrNothing)>, mut scores: Query<&mut Score>) {
-        let Ok((children, settings)) = target.get(trigger.target()) else {
-            // The entity is not scoring for all-or-nothing.
-            return;
-        };
-
+    /// Calculates the sum of the given child scores, or 0 if any of them doesn't reach the threshold.
+    #[must_use]
+    pub fn calculate(&self, child_scores: impl IntoIterator<Item = Score>) -> Score {
         let mut sum: f32 = 0.;
 
-        for child_score in scores.iter_many(children) {
-            if *child_score < settings.threshold() {
+        for child_score in child_scores {
+            if child_score < self.threshold() {
                 sum = 0.;
                 break;
             }
             sum += child_score.get();
         }
 
-        let Ok(mut actor_score) = scores.get_mut(trigger.target()) else {
-            // The entity is not scoring.
-            return;
-        };
-
-        actor_score.set(sum);
+        Score::new(sum)
     }
 }
 
-impl Component for AllOrNothing {
-    type Mutability = Mutable;
-    const STORAGE_TYPE: StorageType = StorageType::Table;
-
-    fn register_component_hooks(hooks: &mut ComponentHooks) {
-        hooks.on_add(|mut world, _ctx| {
-            #[derive(Resource, Default)]
-            struct AllOrNothingObserverSpawned;
-
-            world
-                .commands()
-                .once::<AllOrNothingObserverSpawned>()
-                .observe(Self::observer);
-        });
+impl ChildAggregateScorer for AllOrNothing {
+    fn aggregate(&self, children: impl Iterator<Item = Score>) -> Score {
+        self.calculate(children)
     }
 }
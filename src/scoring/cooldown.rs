@@ -0,0 +1,216 @@
+use std::time::Duration;
+
+use bevy::{
+    ecs::component::{ComponentHooks, Mutable, StorageType},
+    prelude::*,
+};
+
+use crate::{
+    ecs::CommandsExt,
+    event::{OnActionEnded, OnScorePostProcess},
+    picking::Picker,
+    scoring::{ComparingTargets, Score},
+};
+
+/// [`Component`] for [`Score`] entities that forces their score to zero for a while
+/// after the action they're mapped to in their [`Picker`] has ended.
+///
+/// The cooldown is started by [`OnActionEnded`] for the action mapped to the score entity
+/// in the [`Picker`] of the targeted actor entity, whether the action completed or was cancelled.
+/// While it's active, the score is set to zero in [`OnScorePostProcess`].
+///
+/// - [`CooldownLength::Time`] cooldowns are measured with the [`Time`] [`Resource`],
+///   which is [`Time<Fixed>`] when scoring in a fixed schedule like the [`RealtimeLifecyclePlugin`] does.
+///   Without a [`Time`] resource, they never become active.
+/// - [`CooldownLength::Turns`] cooldowns last for a number of scoring runs of the score entity,
+///   i.e. turns when triggering [`RunScoring`] once per turn in turn-based games.
+///   Every run counts once, even when scored per [`Targets`](crate::scoring::Targets) candidate.
+///   That includes targeted [`RunScoring`] of the entity's tree and the re-scoring done by
+///   [`FailurePolicy::Penalize`](crate::acting::FailurePolicy::Penalize), so extra runs end the cooldown early.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_observed_utility::prelude::*;
+///
+/// # let mut app = App::new();
+/// # app.add_plugins(ObservedUtilityPlugins::TurnBased);
+/// # let mut world = app.world_mut();
+/// #[derive(Component)]
+/// pub struct Drink;
+/// #[derive(Component)]
+/// pub struct Idle;
+///
+/// let drink = world.register_component::<Drink>();
+/// let idle = world.register_component::<Idle>();
+///
+/// // Don't drink again for 2 turns.
+/// let thirst = world
+///     .spawn((FixedScore::new(0.8), Score::default(), Cooldown::turns(2)))
+///     .id();
+/// let actor = world
+///     .spawn((Picker::new(idle).with(thirst, drink), Highest))
+///     .add_child(thirst)
+///     .id();
+/// # world.flush();
+///
+/// world.trigger_targets(OnActionEnded::completed(drink), TargetedAction(actor, drink));
+/// # world.flush();
+///
+/// for _ in 0..2 {
+///     world.trigger(RunScoring);
+///     # world.flush();
+///     assert_eq!(0., world.get::<Score>(thirst).unwrap().get());
+/// }
+///
+/// world.trigger(RunScoring);
+/// # world.flush();
+/// assert_eq!(0.8, world.get::<Score>(thirst).unwrap().get());
+/// ```
+///
+/// [`RealtimeLifecyclePlugin`]: crate::RealtimeLifecyclePlugin
+/// [`RunScoring`]: crate::event::RunScoring
+#[derive(Reflect)]
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[reflect(Component, PartialEq, Debug)]
+pub struct Cooldown {
+    /// How long the cooldown lasts.
+    pub length: CooldownLength,
+    /// The elapsed [`Time`] when a time-based cooldown ends.
+    ends_at: Option<Duration>,
+    /// The remaining scoring runs of a turn-based cooldown.
+    turns_left: u32,
+}
+
+impl Cooldown {
+    /// Creates a new inactive [`Cooldown`] with the given length.
+    #[must_use]
+    pub fn new(length: CooldownLength) -> Self {
+        Self {
+            length,
+            ends_at: None,
+            turns_left: 0,
+        }
+    }
+
+    /// Creates a new inactive [`Cooldown`] lasting for the given time.
+    #[must_use]
+    pub fn time(duration: Duration) -> Self {
+        Self::new(CooldownLength::Time(duration))
+    }
+
+    /// Creates a new inactive [`Cooldown`] lasting for the given number of scoring runs.
+    #[must_use]
+    pub fn turns(turns: u32) -> Self {
+        Self::new(CooldownLength::Turns(turns))
+    }
+
+    /// Starts the cooldown at the given elapsed time, restarting it if it's already active.
+    pub fn start(&mut self, now: Option<Duration>) {
+        match self.length {
+            CooldownLength::Time(duration) => self.ends_at = now.map(|now| now + duration),
+            CooldownLength::Turns(turns) => self.turns_left = turns,
+        }
+    }
+
+    /// Ends the cooldown early.
+    pub fn reset(&mut self) {
+        self.ends_at = None;
+        self.turns_left = 0;
+    }
+
+    /// Returns `true` if the cooldown is active at the given elapsed time.
+    #[must_use]
+    pub fn is_active(&self, now: Option<Duration>) -> bool {
+        match self.length {
+            CooldownLength::Time(_) => self.ends_at.zip(now).is_some_and(|(ends_at, now)| now < ends_at),
+            CooldownLength::Turns(_) => self.turns_left > 0,
+        }
+    }
+
+    /// Returns the remaining scoring runs of a turn-based cooldown, or `0` for a time-based one.
+    #[must_use]
+    pub fn turns_left(&self) -> u32 {
+        self.turns_left
+    }
+
+    /// Counts down one scoring run of a turn-based cooldown.
+    pub fn tick(&mut self) {
+        self.turns_left = self.turns_left.saturating_sub(1);
+    }
+
+    /// [`Observer`] that starts the [`Cooldown`]s of the score entities mapped to the ended action.
+    fn on_action_ended(
+        trigger: Trigger<OnActionEnded>,
+        pickers: Query<&Picker>,
+        mut cooldowns: Query<&mut Cooldown>,
+        time: Option<Res<Time>>,
+    ) {
+        let Ok(picker) = pickers.get(trigger.target()) else {
+            return;
+        };
+        let action = trigger.event().action;
+        let now = time.map(|time| time.elapsed());
+
+        for (&score_entity, _) in picker.choices.iter().filter(|(_, choice)| **choice == action) {
+            if let Ok(mut cooldown) = cooldowns.get_mut(score_entity) {
+                cooldown.start(now);
+            }
+        }
+    }
+
+    /// [`Observer`] that zeroes the [`Score`] while the [`Cooldown`] is active.
+    fn on_post_process(
+        trigger: Trigger<OnScorePostProcess>,
+        mut targets: Query<(&mut Score, &mut Cooldown)>,
+        time: Option<Res<Time>>,
+        comparing: Option<Res<ComparingTargets>>,
+    ) {
+        let Ok((mut score, mut cooldown)) = targets.get_mut(trigger.target()) else {
+            // The entity has no cooldown.
+            return;
+        };
+
+        if cooldown.is_active(time.map(|time| time.elapsed())) {
+            score.set(0.);
+        }
+        if cooldown.turns_left > 0 && comparing.is_none() {
+            // Only mutate while counting down, to avoid triggering change detection
+            cooldown.tick();
+        }
+    }
+}
+
+impl Component for Cooldown {
+    type Mutability = Mutable;
+    const STORAGE_TYPE: StorageType = StorageType::Table;
+
+    fn register_component_hooks(hooks: &mut ComponentHooks) {
+        hooks.on_add(|mut world, _ctx| {
+            #[derive(Resource, Default)]
+            struct CooldownActionObserverSpawned;
+            #[derive(Resource, Default)]
+            struct CooldownScoreObserverSpawned;
+
+            let mut commands = world.commands();
+            commands
+                .once::<CooldownActionObserverSpawned>()
+                .observe(Self::on_action_ended);
+            commands
+                .once::<CooldownScoreObserverSpawned>()
+                .observe(Self::on_post_process);
+        });
+    }
+}
+
+/// How long a [`Cooldown`] lasts.
+#[derive(Reflect)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[reflect(PartialEq, Debug)]
+pub enum CooldownLength {
+    /// Lasts for the given time.
+    Time(Duration),
+    /// Lasts for the given number of scoring runs of the score entity, see [`Cooldown`].
+    Turns(u32),
+}
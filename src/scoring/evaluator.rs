@@ -1,18 +1,40 @@
+use std::{any::Any, sync::Arc};
+
 use bevy::{
     ecs::component::{ComponentHooks, Mutable, StorageType},
     prelude::*,
+    reflect::{
+        FromType, TypeRegistry,
+        serde::{
+            DeserializeWithRegistry, ReflectDeserializeWithRegistry, ReflectSerializeWithRegistry,
+            SerializeWithRegistry,
+        },
+    },
+};
+use serde::{Deserializer, Serializer, de::Error};
+
+use crate::{
+    ecs::CommandsExt,
+    event::{OnScore, OnScorePostProcess},
+    scoring::{
+        ParallelPostProcessor, ParallelScorer, Score, ScoreTree, Scorer, Weighted, deserialize_registered,
+        serialize_registered,
+    },
 };
-
-use crate::{ecs::CommandsExt, event::OnScore, scoring::Score};
 
 /// [`Score`] [`Component`] that uses an [`Evaluator`] to score a single child entity.
 ///
+/// To curve the score of an entity in place instead, without an extra child entity, see [`PostEvaluated`].
+///
 /// # Provided Evaluators
 ///
 /// - [`LinearEvaluator`]: A linear evaluator.
 /// - [`PowerEvaluator`]: A power evaluator.
 /// - [`SigmoidEvaluator`]: A sigmoid evaluator.
 /// - [`ExponentialEvaluator`]: An exponential evaluator.
+/// - [`LogarithmicEvaluator`]: A logarithmic evaluator.
+/// - [`PiecewiseLinearEvaluator`]: Linearly interpolates between sorted keyframes.
+/// - [`CubicSplineEvaluator`]: Smoothly interpolates between sorted keyframes with tangents.
 /// - Any [`Fn`] that takes a single `f32` input and returns a `f32` output.
 ///
 /// # Example
@@ -37,9 +59,18 @@ use crate::{ecs::CommandsExt, event::OnScore, scoring::Score};
 /// # world.flush();
 /// # assert_relative_eq!(world.get::<Score>(scorer).unwrap().get(), 0.49);
 /// ```
+///
+/// # Reflection
+///
+/// [`Evaluated`] is reflected as a struct holding its [`Evaluator`], which can be replaced through reflection.
+/// It's serialized as its [`Evaluator`], which must be registered with `#[reflect(Evaluator)]`
+/// like the provided evaluators are. Evaluators that aren't registered, like closures, can't be serialized.
+#[derive(Reflect, Scorer, Clone)]
+#[reflect(Component, Clone, SerializeWithRegistry, DeserializeWithRegistry)]
+#[scorer(observer = Self::observer)]
 pub struct Evaluated {
     /// The evaluator to use for scoring.
-    evaluator: Box<dyn Evaluator>,
+    evaluator: Arc<dyn Evaluator>,
 }
 
 impl Evaluated {
@@ -47,7 +78,7 @@ impl Evaluated {
     #[must_use]
     pub fn new(evaluator: impl Evaluator) -> Self {
         Self {
-            evaluator: Box::new(evaluator),
+            evaluator: Arc::new(evaluator),
         }
     }
 
@@ -65,17 +96,17 @@ impl Evaluated {
 
     /// Sets the [`Evaluator`] used for scoring.
     pub fn set_evaluator(&mut self, evaluator: impl Evaluator) {
-        self.evaluator = Box::new(evaluator);
+        self.evaluator = Arc::new(evaluator);
     }
 
     /// [`Observer`] for [`Evaluated`] [`Score`] entities that scores a single child [`Score`] entity.
-    fn observer(trigger: Trigger<OnScore>, target: Query<(&Children, &Evaluated)>, mut scores: Query<&mut Score>) {
-        let Ok((children, settings)) = target.get(trigger.target()) else {
+    fn observer(trigger: Trigger<OnScore>, target: Query<&Evaluated>, tree: ScoreTree, mut scores: Query<&mut Score>) {
+        let Ok(settings) = target.get(trigger.target()) else {
             // The entity is not scoring for evaluated.
             return;
         };
 
-        if let &[child] = &**children {
+        if let &[child] = tree.children(trigger.target()) {
             let Ok(child_score) = scores.get_mut(child) else {
                 return;
             };
@@ -89,30 +120,253 @@ impl Evaluated {
     }
 }
 
-impl Component for Evaluated {
+impl ParallelScorer for Evaluated {
+    fn score(&self, children: &[(Score, Weighted)]) -> Option<Score> {
+        if let &[(child_score, _)] = children {
+            Some(Score::new(self.evaluate(child_score.get())))
+        } else {
+            None
+        }
+    }
+}
+
+/// [`Score`] [`Component`] that uses an [`Evaluator`] to curve the entity's own [`Score`],
+/// after it has been calculated by its scorer (like [`Measured`] or [`Sum`]).
+///
+/// See [`Evaluated`] for the list of provided evaluators.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_observed_utility::prelude::*;
+/// # use approx::assert_relative_eq;
+///
+/// # let mut app = App::new();
+/// # app.add_plugins(ObservedUtilityPlugins::RealTime);
+/// # let mut world = app.world_mut();
+/// # let mut commands = world.commands();
+/// # let scorer =
+/// commands
+///     .spawn((Sum::new(0.), PostEvaluated::new(PowerEvaluator::default()), Score::default()))
+///     .with_children(|parent| {
+///         parent.spawn((FixedScore::new(0.4), Score::default()));
+///         parent.spawn((FixedScore::new(0.3), Score::default()));
+///     })
+/// #   .id();
+/// # commands.trigger_targets(RunScoring, scorer);
+/// # world.flush();
+/// # assert_relative_eq!(world.get::<Score>(scorer).unwrap().get(), 0.49);
+/// ```
+///
+/// [`PostEvaluated`] is reflected and serialized like [`Evaluated`].
+///
+/// [`Measured`]: crate::scoring::Measured
+/// [`Sum`]: crate::scoring::Sum
+#[derive(Reflect, Clone)]
+#[reflect(Component, Clone, SerializeWithRegistry, DeserializeWithRegistry)]
+pub struct PostEvaluated {
+    /// The evaluator to use for post-processing.
+    evaluator: Arc<dyn Evaluator>,
+}
+
+impl PostEvaluated {
+    /// Creates a new [`PostEvaluated`] from the given evaluator.
+    #[must_use]
+    pub fn new(evaluator: impl Evaluator) -> Self {
+        Self {
+            evaluator: Arc::new(evaluator),
+        }
+    }
+
+    /// Uses the [`Evaluator`] to evaluate the given value.
+    #[must_use]
+    pub fn evaluate(&self, value: f32) -> f32 {
+        self.evaluator.evaluate(value)
+    }
+
+    /// Returns the [`Evaluator`] used for post-processing.
+    #[must_use]
+    pub fn evaluator(&self) -> &dyn Evaluator {
+        self.evaluator.as_ref()
+    }
+
+    /// Sets the [`Evaluator`] used for post-processing.
+    pub fn set_evaluator(&mut self, evaluator: impl Evaluator) {
+        self.evaluator = Arc::new(evaluator);
+    }
+
+    /// [`Observer`] for [`PostEvaluated`] [`Score`] entities that curves their own freshly calculated [`Score`].
+    fn observer(trigger: Trigger<OnScorePostProcess>, mut target: Query<(&mut Score, &PostEvaluated)>) {
+        let Ok((mut score, settings)) = target.get_mut(trigger.target()) else {
+            // The entity is not post-processing for evaluated.
+            return;
+        };
+
+        let value = settings.evaluate(score.get());
+        score.set(value);
+    }
+}
+
+impl Component for PostEvaluated {
     type Mutability = Mutable;
     const STORAGE_TYPE: StorageType = StorageType::Table;
 
     fn register_component_hooks(hooks: &mut ComponentHooks) {
         hooks.on_add(|mut world, _ctx| {
             #[derive(Resource, Default)]
-            struct EvaluatedObserverSpawned;
+            struct PostEvaluatedObserverSpawned;
 
             world
                 .commands()
-                .once::<EvaluatedObserverSpawned>()
+                .once::<PostEvaluatedObserverSpawned>()
                 .observe(Self::observer);
         });
     }
 }
 
+impl ParallelPostProcessor for PostEvaluated {
+    fn post_process(&self, score: Score) -> Score {
+        Score::new(self.evaluate(score.get()))
+    }
+}
+
+impl SerializeWithRegistry for Evaluated {
+    fn serialize<S: Serializer>(&self, serializer: S, registry: &TypeRegistry) -> Result<S::Ok, S::Error> {
+        serialize_evaluator(self.evaluator.as_ref(), serializer, registry)
+    }
+}
+
+impl<'de> DeserializeWithRegistry<'de> for Evaluated {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D, registry: &TypeRegistry) -> Result<Self, D::Error> {
+        let evaluator = deserialize_evaluator(deserializer, registry)?;
+        Ok(Self { evaluator })
+    }
+}
+
+impl SerializeWithRegistry for PostEvaluated {
+    fn serialize<S: Serializer>(&self, serializer: S, registry: &TypeRegistry) -> Result<S::Ok, S::Error> {
+        serialize_evaluator(self.evaluator.as_ref(), serializer, registry)
+    }
+}
+
+impl<'de> DeserializeWithRegistry<'de> for PostEvaluated {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D, registry: &TypeRegistry) -> Result<Self, D::Error> {
+        let evaluator = deserialize_evaluator(deserializer, registry)?;
+        Ok(Self { evaluator })
+    }
+}
+
+/// Serializes the [`Evaluator`] along with its type path.
+fn serialize_evaluator<S: Serializer>(
+    evaluator: &dyn Evaluator,
+    serializer: S,
+    registry: &TypeRegistry,
+) -> Result<S::Ok, S::Error> {
+    let type_id = (evaluator as &dyn Any).type_id();
+    let reflected = registry
+        .get_type_data::<ReflectEvaluator>(type_id)
+        .and_then(|reflect_evaluator| reflect_evaluator.as_reflect(evaluator));
+    serialize_registered(reflected, "Evaluator", serializer, registry)
+}
+
+/// Deserializes an [`Evaluator`] serialized by [`serialize_evaluator`], using its [`ReflectEvaluator`] type data.
+fn deserialize_evaluator<'de, D: Deserializer<'de>>(
+    deserializer: D,
+    registry: &TypeRegistry,
+) -> Result<Arc<dyn Evaluator>, D::Error> {
+    let (value, registration) = deserialize_registered(deserializer, registry)?;
+    registration
+        .data::<ReflectEvaluator>()
+        .and_then(|reflect_evaluator| reflect_evaluator.get_boxed(value).ok())
+        .map(Arc::from)
+        .ok_or_else(|| {
+            D::Error::custom(format_args!(
+                "`{}` is not an evaluator, is it registered with `#[reflect(Evaluator)]`?",
+                registration.type_info().type_path()
+            ))
+        })
+}
+
 /// Curves values within a certain range.
-#[reflect_trait]
-pub trait Evaluator: Send + Sync + 'static {
+pub trait Evaluator: Any + Send + Sync {
     /// Evaluates the input value and returns an output value.
     fn evaluate(&self, value: f32) -> f32;
 }
 
+impl TypePath for dyn Evaluator {
+    fn type_path() -> &'static str {
+        "dyn bevy_observed_utility::scoring::Evaluator"
+    }
+
+    fn short_type_path() -> &'static str {
+        "dyn Evaluator"
+    }
+}
+
+/// A boxed [`Evaluator`], or the reflected value that isn't one.
+type BoxedEvaluator = Result<Box<dyn Evaluator>, Box<dyn Reflect>>;
+
+/// Type data for [`Evaluator`]s, registered with `#[reflect(Evaluator)]`.
+///
+/// Like the type data generated by `#[reflect_trait]`, this casts reflected values to [`Evaluator`]s.
+/// It also casts [`Evaluator`]s back to reflected values, which is how [`Evaluated`] and [`PostEvaluated`] are serialized.
+#[derive(Clone)]
+pub struct ReflectEvaluator {
+    get_func: fn(&dyn Reflect) -> Option<&dyn Evaluator>,
+    get_mut_func: fn(&mut dyn Reflect) -> Option<&mut dyn Evaluator>,
+    get_boxed_func: fn(Box<dyn Reflect>) -> BoxedEvaluator,
+    as_reflect_func: fn(&dyn Evaluator) -> Option<&dyn Reflect>,
+}
+
+impl ReflectEvaluator {
+    /// Downcasts a reflected value to an [`Evaluator`], if it's of the registered type.
+    #[must_use]
+    pub fn get<'a>(&self, reflect_value: &'a dyn Reflect) -> Option<&'a dyn Evaluator> {
+        (self.get_func)(reflect_value)
+    }
+
+    /// Downcasts a mutable reflected value to an [`Evaluator`], if it's of the registered type.
+    #[must_use]
+    pub fn get_mut<'a>(&self, reflect_value: &'a mut dyn Reflect) -> Option<&'a mut dyn Evaluator> {
+        (self.get_mut_func)(reflect_value)
+    }
+
+    /// Downcasts a boxed reflected value to an [`Evaluator`], if it's of the registered type.
+    ///
+    /// # Errors
+    ///
+    /// Returns the value back if it's of another type.
+    pub fn get_boxed(&self, reflect_value: Box<dyn Reflect>) -> Result<Box<dyn Evaluator>, Box<dyn Reflect>> {
+        (self.get_boxed_func)(reflect_value)
+    }
+
+    /// Casts an [`Evaluator`] back to a reflected value, if it's of the registered type.
+    #[must_use]
+    pub fn as_reflect<'a>(&self, evaluator: &'a dyn Evaluator) -> Option<&'a dyn Reflect> {
+        (self.as_reflect_func)(evaluator)
+    }
+}
+
+impl<T: Evaluator + Reflect> FromType<T> for ReflectEvaluator {
+    fn from_type() -> Self {
+        Self {
+            get_func: |reflect_value| reflect_value.downcast_ref::<T>().map(|value| value as &dyn Evaluator),
+            get_mut_func: |reflect_value| {
+                reflect_value
+                    .downcast_mut::<T>()
+                    .map(|value| value as &mut dyn Evaluator)
+            },
+            get_boxed_func: |reflect_value| reflect_value.downcast::<T>().map(|value| value as Box<dyn Evaluator>),
+            as_reflect_func: |evaluator| {
+                (evaluator as &dyn Any)
+                    .downcast_ref::<T>()
+                    .map(|value| value as &dyn Reflect)
+            },
+        }
+    }
+}
+
 /// [`Evaluator`] that uses a linear function to transform a value.
 #[derive(Reflect, Clone, Copy, PartialEq, Debug)]
 #[reflect(Evaluator, PartialEq, Debug)]
@@ -355,6 +609,148 @@ impl Evaluator for LogarithmicEvaluator {
     }
 }
 
+/// [`Evaluator`] that linearly interpolates between sorted `(x, y)` keyframes.
+///
+/// Values before the first keyframe or after the last keyframe are clamped to their `y` values,
+/// and NaN evaluates to the first keyframe's `y` value. With no keyframes, every value evaluates to `0`.
+#[derive(Reflect, Clone, PartialEq, Debug, Default)]
+#[reflect(Evaluator, PartialEq, Debug, Default)]
+pub struct PiecewiseLinearEvaluator {
+    keyframes: Vec<Vec2>,
+}
+
+impl PiecewiseLinearEvaluator {
+    /// Creates a new piecewise linear evaluator from the given keyframes, sorting them by `x`.
+    #[must_use]
+    pub fn new(keyframes: impl IntoIterator<Item = Vec2>) -> Self {
+        let mut keyframes: Vec<Vec2> = keyframes.into_iter().collect();
+        keyframes.sort_by(|a, b| a.x.total_cmp(&b.x));
+        Self { keyframes }
+    }
+
+    /// Returns the keyframes, sorted by `x`.
+    #[must_use]
+    pub fn keyframes(&self) -> &[Vec2] {
+        &self.keyframes
+    }
+}
+
+impl Evaluator for PiecewiseLinearEvaluator {
+    fn evaluate(&self, value: f32) -> f32 {
+        let (Some(first), Some(last)) = (self.keyframes.first(), self.keyframes.last()) else {
+            return 0.;
+        };
+        if value <= first.x || value.is_nan() {
+            // NaN can't be placed between keyframes
+            return first.y;
+        }
+        if value >= last.x {
+            return last.y;
+        }
+
+        // The first keyframe past the value, which is never the first keyframe due to the checks above.
+        let i = self.keyframes.partition_point(|k| k.x <= value);
+        let (a, b) = (self.keyframes[i - 1], self.keyframes[i]);
+        a.y + (b.y - a.y) * (value - a.x) / (b.x - a.x)
+    }
+}
+
+/// A keyframe of a [`CubicSplineEvaluator`].
+#[derive(Reflect, Clone, Copy, PartialEq, Debug, Default)]
+#[reflect(PartialEq, Debug, Default)]
+pub struct CubicKeyframe {
+    /// The `(x, y)` position of the keyframe.
+    pub position: Vec2,
+    /// The slope (`dy/dx`) of the curve at the keyframe.
+    pub tangent: f32,
+}
+
+impl CubicKeyframe {
+    /// Creates a new keyframe at the given position with the given tangent.
+    #[must_use]
+    pub fn new(position: Vec2, tangent: f32) -> Self {
+        Self { position, tangent }
+    }
+}
+
+/// [`Evaluator`] that uses cubic Hermite interpolation between sorted keyframes with tangents.
+///
+/// Values before the first keyframe or after the last keyframe are clamped to their `y` values,
+/// and NaN evaluates to the first keyframe's `y` value. With no keyframes, every value evaluates to `0`.
+#[derive(Reflect, Clone, PartialEq, Debug, Default)]
+#[reflect(Evaluator, PartialEq, Debug, Default)]
+pub struct CubicSplineEvaluator {
+    keyframes: Vec<CubicKeyframe>,
+}
+
+impl CubicSplineEvaluator {
+    /// Creates a new cubic spline evaluator from the given keyframes, sorting them by `x`.
+    #[must_use]
+    pub fn new(keyframes: impl IntoIterator<Item = CubicKeyframe>) -> Self {
+        let mut keyframes: Vec<CubicKeyframe> = keyframes.into_iter().collect();
+        keyframes.sort_by(|a, b| a.position.x.total_cmp(&b.position.x));
+        Self { keyframes }
+    }
+
+    /// Creates a cubic spline evaluator passing through the given points,
+    /// with tangents calculated from the neighboring points (Catmull-Rom style).
+    #[must_use]
+    pub fn from_points(points: impl IntoIterator<Item = Vec2>) -> Self {
+        let mut points: Vec<Vec2> = points.into_iter().collect();
+        points.sort_by(|a, b| a.x.total_cmp(&b.x));
+
+        let keyframes = (0..points.len()).map(|i| {
+            let prev = points[i.saturating_sub(1)];
+            let next = points[(i + 1).min(points.len() - 1)];
+            let tangent = if next.x > prev.x {
+                (next.y - prev.y) / (next.x - prev.x)
+            } else {
+                0.
+            };
+            CubicKeyframe::new(points[i], tangent)
+        });
+        Self::new(keyframes)
+    }
+
+    /// Returns the keyframes, sorted by `x`.
+    #[must_use]
+    pub fn keyframes(&self) -> &[CubicKeyframe] {
+        &self.keyframes
+    }
+}
+
+impl Evaluator for CubicSplineEvaluator {
+    fn evaluate(&self, value: f32) -> f32 {
+        let (Some(first), Some(last)) = (self.keyframes.first(), self.keyframes.last()) else {
+            return 0.;
+        };
+        if value <= first.position.x || value.is_nan() {
+            // NaN can't be placed between keyframes
+            return first.position.y;
+        }
+        if value >= last.position.x {
+            return last.position.y;
+        }
+
+        // The first keyframe past the value, which is never the first keyframe due to the checks above.
+        let i = self.keyframes.partition_point(|k| k.position.x <= value);
+        let (a, b) = (self.keyframes[i - 1], self.keyframes[i]);
+
+        let dx = b.position.x - a.position.x;
+        let t = (value - a.position.x) / dx;
+        let t2 = t * t;
+        let t3 = t2 * t;
+
+        // Hermite basis functions
+        let h00 = 2. * t3 - 3. * t2 + 1.;
+        let h10 = t3 - 2. * t2 + t;
+        let h01 = -2. * t3 + 3. * t2;
+        let h11 = t3 - t2;
+
+        h00 * a.position.y + h10 * dx * a.tangent + h01 * b.position.y + h11 * dx * b.tangent
+    }
+}
+
 impl<F> Evaluator for F
 where
     F: Fn(f32) -> f32 + Send + Sync + 'static,
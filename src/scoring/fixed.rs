@@ -1,9 +1,9 @@
-use bevy::{
-    ecs::component::{ComponentHooks, Mutable, StorageType},
-    prelude::*,
-};
+use bevy::prelude::*;
 
-use crate::{ecs::CommandsExt, event::OnScore, scoring::Score};
+use crate::{
+    event::OnScore,
+    scoring::{ParallelScorer, Score, Scorer, Weighted},
+};
 
 /// [`Score`] [`Component`] that always scores a fixed value.
 ///
@@ -25,8 +25,9 @@ use crate::{ecs::CommandsExt, event::OnScore, scoring::Score};
 /// # world.flush();
 /// # assert_eq!(world.get::<Score>(scorer).unwrap().get(), 0.5);
 /// ```
-#[derive(Reflect, Clone, Copy, PartialEq, Debug, Default)]
+#[derive(Reflect, Scorer, Clone, Copy, PartialEq, Debug, Default)]
 #[reflect(Component, PartialEq, Debug, Default)]
+#[scorer(observer = Self::observer)]
 pub struct FixedScore {
     /// The fixed value to score.
     value: Score,
@@ -61,19 +62,8 @@ impl FixedScore {
     }
 }
 
-impl Component for FixedScore {
-    type Mutability = Mutable;
-    const STORAGE_TYPE: StorageType = StorageType::Table;
-
-    fn register_component_hooks(hooks: &mut ComponentHooks) {
-        hooks.on_add(|mut world, _ctx| {
-            #[derive(Resource, Default)]
-            struct FixedScoreObserverSpawned;
-
-            world
-                .commands()
-                .once::<FixedScoreObserverSpawned>()
-                .observe(Self::observer);
-        });
+impl ParallelScorer for FixedScore {
+    fn score(&self, _children: &[(Score, Weighted)]) -> Option<Score> {
+        Some(self.value())
     }
 }
@@ -0,0 +1,178 @@
+use std::ops::Deref;
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+use crate::scoring::Score;
+
+/// [`Relationship`](bevy::ecs::relationship::Relationship) [`Component`] for [`Score`](crate::scoring::Score) entities
+/// that belong to the scoring tree of another entity, i.e. a parent [`Score`](crate::scoring::Score) entity
+/// or an actor entity with a [`Picker`](crate::picking::Picker).
+///
+/// Unlike [`ChildOf`], this keeps scoring trees apart from other hierarchies like transforms and UI,
+/// so that actors can have ordinary children that aren't scored.
+/// Despawning the parent entity despawns its [`Scorers`], like it does its [`Children`].
+///
+/// See [`ScoreHierarchy`] for how this works alongside [`ChildOf`].
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_observed_utility::prelude::*;
+///
+/// # let mut app = App::new();
+/// # app.add_plugins(ObservedUtilityPlugins::TurnBased);
+/// # let mut world = app.world_mut();
+/// let scorer = world
+///     .spawn((Sum::new(0.), Score::default()))
+///     .with_related_entities::<ScoreOf>(|parent| {
+///         parent.spawn((FixedScore::new(0.7), Score::default()));
+///         parent.spawn((FixedScore::new(0.2), Score::default()));
+///     })
+///     .id();
+///
+/// world.trigger_targets(RunScoring, scorer);
+/// # world.flush();
+/// assert_eq!(0.9, world.get::<Score>(scorer).unwrap().get());
+/// ```
+#[derive(Component, Reflect)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[reflect(Component, PartialEq, Debug, FromWorld)]
+#[relationship(relationship_target = Scorers)]
+pub struct ScoreOf(#[entities] pub Entity);
+
+impl FromWorld for ScoreOf {
+    fn from_world(_world: &mut World) -> Self {
+        // Only needed for reflection, which patches in the real entity afterwards.
+        ScoreOf(Entity::PLACEHOLDER)
+    }
+}
+
+/// [`RelationshipTarget`](bevy::ecs::relationship::RelationshipTarget) [`Component`] listing the
+/// [`ScoreOf`] entities in the scoring tree of this entity, in order.
+///
+/// This is maintained by Bevy, so modify the [`ScoreOf`] components instead.
+#[derive(Component, Reflect)]
+#[derive(PartialEq, Eq, Debug, Default)]
+#[reflect(Component, PartialEq, Debug, Default)]
+#[relationship_target(relationship = ScoreOf, linked_spawn)]
+pub struct Scorers(Vec<Entity>);
+
+impl Deref for Scorers {
+    type Target = [Entity];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<'a> IntoIterator for &'a Scorers {
+    type Item = &'a Entity;
+    type IntoIter = std::slice::Iter<'a, Entity>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+/// [`Resource`] configuring which relationships make up scoring trees,
+/// set with [`ScoringPlugin::hierarchy`](crate::scoring::ScoringPlugin::hierarchy).
+///
+/// This is followed when scoring, when finding ancestors with an [`AncestorQuery`](crate::ecs::AncestorQuery),
+/// and when picking between the scored children of an actor entity.
+#[derive(Resource, Reflect)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[reflect(Resource, PartialEq, Debug, Default)]
+pub enum ScoreHierarchy {
+    /// Entities with [`Scorers`] use them as their scored children, and other entities use their [`Children`].
+    /// Likewise, the parent of an entity is its [`ScoreOf`] if it has one, or its [`ChildOf`] otherwise.
+    ///
+    /// This allows migrating scoring trees from [`ChildOf`] to [`ScoreOf`] one entity at a time.
+    #[default]
+    ScorersOrChildren,
+    /// Only [`ScoreOf`] and [`Scorers`] make up scoring trees, and [`ChildOf`] and [`Children`] are ignored.
+    Scorers,
+}
+
+impl ScoreHierarchy {
+    /// Returns the scored children of the given entity.
+    #[must_use]
+    pub fn children<'a>(self, entity: &EntityRef<'a>) -> &'a [Entity] {
+        match (entity.get::<Scorers>(), self) {
+            (Some(scorers), _) => scorers,
+            (None, ScoreHierarchy::ScorersOrChildren) => entity.get::<Children>().map_or(&[], |children| children),
+            (None, ScoreHierarchy::Scorers) => &[],
+        }
+    }
+
+    /// Returns the parent of the given entity in its scoring tree, if any.
+    #[must_use]
+    pub fn parent(self, entity: &EntityRef) -> Option<Entity> {
+        match (entity.get::<ScoreOf>(), self) {
+            (Some(score_of), _) => Some(score_of.0),
+            (None, ScoreHierarchy::ScorersOrChildren) => entity.get::<ChildOf>().map(ChildOf::parent),
+            (None, ScoreHierarchy::Scorers) => None,
+        }
+    }
+
+    /// Returns the [`ScoreHierarchy`] of the given world, or the default if it has none.
+    #[must_use]
+    pub fn of(world: &World) -> Self {
+        world.get_resource::<Self>().copied().unwrap_or_default()
+    }
+}
+
+/// [`SystemParam`] for navigating scoring trees, following the [`ScoreHierarchy`].
+#[derive(SystemParam)]
+pub struct ScoreTree<'w, 's> {
+    hierarchy: Option<Res<'w, ScoreHierarchy>>,
+    children: Query<'w, 's, (Option<&'static Scorers>, Option<&'static Children>)>,
+    parents: Query<'w, 's, (Option<&'static ScoreOf>, Option<&'static ChildOf>)>,
+}
+
+impl ScoreTree<'_, '_> {
+    /// Returns the [`ScoreHierarchy`] being followed.
+    #[must_use]
+    pub fn hierarchy(&self) -> ScoreHierarchy {
+        self.hierarchy.as_deref().copied().unwrap_or_default()
+    }
+
+    /// Returns the scored children of the given entity.
+    #[must_use]
+    pub fn children(&self, entity: Entity) -> &[Entity] {
+        match (self.children.get(entity), self.hierarchy()) {
+            (Ok((Some(scorers), _)), _) => scorers,
+            (Ok((None, Some(children))), ScoreHierarchy::ScorersOrChildren) => children,
+            _ => &[],
+        }
+    }
+
+    /// Returns the parent of the given entity in its scoring tree, if any.
+    #[must_use]
+    pub fn parent(&self, entity: Entity) -> Option<Entity> {
+        match (self.parents.get(entity), self.hierarchy()) {
+            (Ok((Some(score_of), _)), _) => Some(score_of.0),
+            (Ok((None, Some(child_of))), ScoreHierarchy::ScorersOrChildren) => Some(child_of.parent()),
+            _ => None,
+        }
+    }
+}
+
+/// [`SystemParam`] for finding the roots of scoring trees, i.e. [`Score`] entities without a [`Score`] parent.
+#[derive(SystemParam)]
+pub struct ScoreRoots<'w, 's> {
+    scored: Query<'w, 's, Entity, With<Score>>,
+    unscored: Query<'w, 's, (), Without<Score>>,
+    tree: ScoreTree<'w, 's>,
+}
+
+impl ScoreRoots<'_, '_> {
+    /// Returns all [`Score`] entities that have no parent at all, or whose parent is not a [`Score`] entity.
+    pub fn iter(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.scored.iter().filter(|&entity| {
+            self.tree
+                .parent(entity)
+                .is_none_or(|parent| self.unscored.contains(parent))
+        })
+    }
+}
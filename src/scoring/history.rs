@@ -0,0 +1,280 @@
+use std::{collections::VecDeque, fmt::Write, time::Duration};
+
+use bevy::{
+    ecs::component::{ComponentHooks, Mutable, StorageType},
+    prelude::*,
+};
+
+use crate::{
+    TurnQueue,
+    ecs::CommandsExt,
+    event::OnScorePostProcess,
+    scoring::{ComparingTargets, Score, ScoreHierarchy},
+};
+
+/// [`Component`] for [`Score`] entities that keeps their last few scores, e.g. to plot them offline.
+///
+/// A sample is recorded every time the entity is scored, after all post-processing.
+/// Entities scored per [`Targets`](crate::scoring::Targets) candidate record a sample for the best target only.
+/// When the history is full, the oldest sample is dropped.
+///
+/// - [`ScoreClock::Time`] histories are timestamped with the elapsed [`Time`],
+///   which is [`Time<Fixed>`] when scoring in a fixed schedule like the [`RealtimeLifecyclePlugin`] does.
+///   Without a [`Time`] resource, nothing is recorded.
+/// - [`ScoreClock::Turns`] histories are timestamped with the current [`TurnQueue::turn`], so that the histories
+///   of all entities line up. Scoring an entity again during the same turn replaces that turn's sample.
+///   Without a [`TurnQueue`], they're timestamped with the number of scoring runs of the score entity
+///   since the history was added instead.
+///
+/// Trees using [`ScoreHistory`] are always scored one entity at a time, even with
+/// [`ScoringPlugin::parallel`], and entities with a [`ScoreHistory`] are re-scored on every run with
+/// [`ScoringPlugin::incremental`], so that no sample is skipped.
+///
+/// All histories of an actor's score entities can be exported with [`ScoreHistory::to_csv`].
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_observed_utility::prelude::*;
+///
+/// # let mut app = App::new();
+/// # app.add_plugins(ObservedUtilityPlugins::TurnBased);
+/// # let mut world = app.world_mut();
+/// let scorer = world
+///     .spawn((FixedScore::new(0.8), Score::default(), ScoreHistory::turns(2)))
+///     .id();
+/// let actor = world.spawn_empty().add_child(scorer).id();
+/// world.resource_mut::<TurnQueue>().push(actor);
+/// # world.flush();
+///
+/// for _ in 0..3 {
+///     world.trigger(AdvanceTurn);
+///     # world.flush();
+/// }
+///
+/// let history = world.get::<ScoreHistory>(scorer).unwrap();
+/// assert_eq!(
+///     // The first turn was dropped to make room.
+///     vec![(ScoreTimestamp::Turn(2), 0.8), (ScoreTimestamp::Turn(3), 0.8)],
+///     history.samples().map(|(timestamp, score)| (timestamp, score.get())).collect::<Vec<_>>(),
+/// );
+///
+/// let csv = ScoreHistory::to_csv(world, actor);
+/// assert_eq!(Some("entity,timestamp,score"), csv.lines().next());
+/// assert_eq!(3, csv.lines().count());
+/// ```
+///
+/// [`RealtimeLifecyclePlugin`]: crate::RealtimeLifecyclePlugin
+/// [`TurnQueue`]: crate::TurnQueue
+/// [`TurnQueue::turn`]: crate::TurnQueue::turn
+/// [`ScoringPlugin::parallel`]: crate::scoring::ScoringPlugin::parallel
+/// [`ScoringPlugin::incremental`]: crate::scoring::ScoringPlugin::incremental
+#[derive(Reflect)]
+#[derive(Clone, PartialEq, Debug)]
+#[reflect(Component, PartialEq, Debug)]
+pub struct ScoreHistory {
+    /// How the samples are timestamped.
+    pub clock: ScoreClock,
+    /// The maximum number of samples to keep.
+    capacity: usize,
+    /// The recorded samples, oldest first.
+    samples: VecDeque<(ScoreTimestamp, Score)>,
+    /// The number of scoring runs recorded so far, for timestamps without a [`TurnQueue`](crate::TurnQueue).
+    turns: u64,
+}
+
+impl ScoreHistory {
+    /// Creates a new empty [`ScoreHistory`] keeping up to `capacity` samples, timestamped with the given clock.
+    #[must_use]
+    pub fn new(clock: ScoreClock, capacity: usize) -> Self {
+        Self {
+            clock,
+            capacity,
+            samples: VecDeque::with_capacity(capacity),
+            turns: 0,
+        }
+    }
+
+    /// Creates a new empty [`ScoreHistory`] keeping up to `capacity` samples, timestamped with the elapsed [`Time`].
+    #[must_use]
+    pub fn time(capacity: usize) -> Self {
+        Self::new(ScoreClock::Time, capacity)
+    }
+
+    /// Creates a new empty [`ScoreHistory`] keeping up to `capacity` samples, timestamped with turn numbers.
+    #[must_use]
+    pub fn turns(capacity: usize) -> Self {
+        Self::new(ScoreClock::Turns, capacity)
+    }
+
+    /// Returns the maximum number of samples to keep.
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Sets the maximum number of samples to keep, dropping the oldest samples if there are too many.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        while self.samples.len() > capacity {
+            self.samples.pop_front();
+        }
+    }
+
+    /// Returns the recorded samples, oldest first.
+    pub fn samples(&self) -> impl ExactSizeIterator<Item = (ScoreTimestamp, Score)> + '_ {
+        self.samples.iter().copied()
+    }
+
+    /// Returns the most recently recorded sample, if any.
+    #[must_use]
+    pub fn latest(&self) -> Option<(ScoreTimestamp, Score)> {
+        self.samples.back().copied()
+    }
+
+    /// Removes all recorded samples.
+    pub fn clear(&mut self) {
+        self.samples.clear();
+    }
+
+    /// Records a sample of the given score at the given elapsed time, or during the given turn.
+    ///
+    /// Without a turn, turn-based histories count their own scoring runs instead.
+    pub fn record(&mut self, score: Score, now: Option<Duration>, turn: Option<u64>) {
+        let timestamp = match self.clock {
+            ScoreClock::Time => {
+                let Some(now) = now else {
+                    return;
+                };
+                ScoreTimestamp::Time(now)
+            }
+            ScoreClock::Turns => {
+                self.turns += 1;
+                ScoreTimestamp::Turn(turn.unwrap_or(self.turns))
+            }
+        };
+
+        if self.capacity == 0 {
+            return;
+        }
+        if self.latest().is_some_and(|(latest, _)| latest == timestamp) {
+            // Scored again during the same turn
+            self.samples.pop_back();
+        }
+        if self.samples.len() == self.capacity {
+            self.samples.pop_front();
+        }
+        self.samples.push_back((timestamp, score));
+    }
+
+    /// Exports the [`ScoreHistory`] of all [`Score`] descendants of the given actor entity as CSV.
+    ///
+    /// Each row is a sample, with the columns `entity,timestamp,score`.
+    /// Time timestamps are in seconds, and turn timestamps are turn numbers.
+    #[must_use]
+    pub fn to_csv(world: &World, actor: Entity) -> String {
+        let hierarchy = ScoreHierarchy::of(world);
+        let mut csv = String::from("entity,timestamp,score\n");
+        let mut stack = vec![actor];
+
+        while let Some(entity) = stack.pop() {
+            if let Some(history) = world.get::<ScoreHistory>(entity) {
+                for (timestamp, score) in history.samples() {
+                    // Writing to a `String` can't fail.
+                    let _ = writeln!(csv, "{entity},{timestamp},{}", score.get());
+                }
+            }
+            if let Ok(entity_ref) = world.get_entity(entity) {
+                // Reversed so that children are exported in order.
+                stack.extend(
+                    hierarchy
+                        .children(&entity_ref)
+                        .iter()
+                        .rev()
+                        .filter(|&&child| world.get::<Score>(child).is_some()),
+                );
+            }
+        }
+
+        csv
+    }
+
+    /// [`Observer`] that queues recording the entity's [`Score`], so that it's recorded after all post-processing.
+    fn on_post_process(
+        trigger: Trigger<OnScorePostProcess>,
+        mut commands: Commands,
+        histories: Query<(), With<Self>>,
+        comparing: Option<Res<ComparingTargets>>,
+    ) {
+        let entity = trigger.target();
+        if !histories.contains(entity) || comparing.is_some() {
+            // The entity has no history, or is being scored for a target that may not be picked.
+            return;
+        }
+
+        commands.queue(move |world: &mut World| {
+            let now = world.get_resource::<Time>().map(|time| time.elapsed());
+            let turn = world.get_resource::<TurnQueue>().map(TurnQueue::turn);
+            let Ok(mut entity) = world.get_entity_mut(entity) else {
+                return;
+            };
+            let Some(score) = entity.get::<Score>().copied() else {
+                return;
+            };
+            if let Some(mut history) = entity.get_mut::<ScoreHistory>() {
+                history.record(score, now, turn);
+            }
+        });
+    }
+}
+
+impl Component for ScoreHistory {
+    type Mutability = Mutable;
+    const STORAGE_TYPE: StorageType = StorageType::Table;
+
+    fn register_component_hooks(hooks: &mut ComponentHooks) {
+        hooks.on_add(|mut world, _ctx| {
+            #[derive(Resource, Default)]
+            struct ScoreHistoryObserverSpawned;
+
+            world
+                .commands()
+                .once::<ScoreHistoryObserverSpawned>()
+                .observe(Self::on_post_process);
+        });
+    }
+}
+
+/// How a [`ScoreHistory`] timestamps its samples.
+#[derive(Reflect)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[reflect(PartialEq, Debug)]
+pub enum ScoreClock {
+    /// Timestamps samples with the elapsed [`Time`].
+    Time,
+    /// Timestamps samples with the current turn of the [`TurnQueue`](crate::TurnQueue),
+    /// or the number of scoring runs without one.
+    Turns,
+}
+
+/// The timestamp of a [`ScoreHistory`] sample.
+#[derive(Reflect)]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+#[reflect(PartialEq, Debug)]
+pub enum ScoreTimestamp {
+    /// The elapsed [`Time`] when the sample was recorded.
+    Time(Duration),
+    /// The turn the sample was recorded in, or the scoring run without a [`TurnQueue`](crate::TurnQueue),
+    /// starting at 1.
+    Turn(u64),
+}
+
+impl std::fmt::Display for ScoreTimestamp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ScoreTimestamp::Time(elapsed) => write!(f, "{}", elapsed.as_secs_f64()),
+            ScoreTimestamp::Turn(turn) => write!(f, "{turn}"),
+        }
+    }
+}
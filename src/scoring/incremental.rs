@@ -0,0 +1,186 @@
+use bevy::{
+    ecs::{
+        component::{ComponentId, Tick},
+        world::EntityRef,
+    },
+    platform::collections::HashSet,
+    prelude::*,
+};
+
+#[cfg(feature = "rand")]
+use crate::scoring::RandomScore;
+use crate::{
+    event::{OnScore, OnScorePostProcess},
+    scoring::{Cooldown, Score, ScoreHierarchy, ScoreHistory, ScoringOrder, Targets, Weighted, post_order},
+};
+
+/// Marker [`Component`] for [`Score`] entities that are re-scored on every run with
+/// [`ScoringPlugin::incremental`](crate::scoring::ScoringPlugin::incremental),
+/// even if none of their components or children changed.
+///
+/// Add this to scorers that read their inputs from other entities or resources,
+/// like scorers using [`score_ancestor`](crate::scoring::score_ancestor).
+/// Scorers that always need this can instead be registered with [`IncrementalScorers::register_volatile`].
+#[derive(Component, Reflect)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[reflect(Component, PartialEq, Debug, Default)]
+pub struct Volatile;
+
+/// [`Component`] recording the change tick of the last incremental scoring run of the tree rooted at this entity.
+///
+/// Components changed after this tick make their [`Score`] entity dirty on the next run.
+#[derive(Component, Reflect)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[reflect(Component, PartialEq, Debug, Default)]
+pub struct LastScored(Tick);
+
+impl LastScored {
+    /// Returns the change tick of the last incremental scoring run.
+    #[must_use]
+    pub fn tick(&self) -> Tick {
+        self.0
+    }
+}
+
+/// [`Resource`] holding the [`Component`]s that make a [`Score`] entity [`Volatile`] for incremental scoring.
+///
+/// [`Volatile`], [`Cooldown`], [`ScoreHistory`] and [`Targets`] (and `RandomScore`, with the `rand` feature)
+/// are registered by default, and [`ActionPlugin`](crate::acting::ActionPlugin) registers
+/// [`FailurePenalty`](crate::acting::FailurePenalty).
+///
+/// See [`ScoringPlugin::incremental`](crate::scoring::ScoringPlugin::incremental) for more information.
+#[derive(Resource)]
+pub struct IncrementalScorers {
+    volatile: Vec<ComponentId>,
+}
+
+impl FromWorld for IncrementalScorers {
+    fn from_world(world: &mut World) -> Self {
+        let mut this = Self { volatile: Vec::new() };
+        this.add_volatile::<Volatile>(world);
+        this.add_volatile::<Cooldown>(world);
+        this.add_volatile::<ScoreHistory>(world);
+        this.add_volatile::<Targets>(world);
+        #[cfg(feature = "rand")]
+        this.add_volatile::<RandomScore>(world);
+        this
+    }
+}
+
+impl IncrementalScorers {
+    /// Registers the [`Component`] `T` as volatile, so that [`Score`] entities using it are re-scored on every run.
+    pub fn register_volatile<T: Component>(world: &mut World) {
+        let mut this = world
+            .remove_resource::<Self>()
+            .unwrap_or_else(|| Self::from_world(world));
+        this.add_volatile::<T>(world);
+        world.insert_resource(this);
+    }
+
+    fn add_volatile<T: Component>(&mut self, world: &mut World) {
+        let id = world.register_component::<T>();
+        if !self.volatile.contains(&id) {
+            self.volatile.push(id);
+        }
+    }
+
+    /// Scores the given root entities in post-order, skipping [`Score`] entities that are clean.
+    ///
+    /// An entity is dirty, and triggered with [`OnScore`] and [`OnScorePostProcess`], if:
+    /// - its tree has no [`ScoringOrder`] or [`LastScored`] yet, e.g. because the tree changed shape,
+    /// - any of its components other than [`Score`] changed since the last run, like its scorer settings,
+    /// - any of its children's [`Score`] or [`Weighted`] changed in this run, or their [`Score`] changed since the last run,
+    ///   e.g. when set by another system, or
+    /// - it's [volatile](IncrementalScorers::register_volatile).
+    pub fn score(world: &mut World, roots: &[Entity]) {
+        let score = world.register_component::<Score>();
+        let ignored = [
+            score,
+            world.register_component::<ScoringOrder>(),
+            world.register_component::<LastScored>(),
+        ];
+        let weighted = world.register_component::<Weighted>();
+        let volatile = world
+            .get_resource::<Self>()
+            .map(|this| this.volatile.clone())
+            .unwrap_or_default();
+        let hierarchy = ScoreHierarchy::of(world);
+
+        for &root in roots {
+            let cached = world.get::<ScoringOrder>(root).cloned();
+            let last_run = cached
+                .as_ref()
+                .and_then(|_| world.get::<LastScored>(root))
+                .map(LastScored::tick);
+            let fresh = cached.is_none();
+            let order = cached.map_or_else(|| post_order(world, root), |order| order.entities().to_vec());
+            let this_run = world.read_change_tick();
+
+            // The entities whose children's scores or weights changed in this run
+            let mut dirty_parents = HashSet::new();
+            for &entity in &order {
+                let Ok(entity_ref) = world.get_entity(entity) else {
+                    continue;
+                };
+                let dirty = last_run.is_none_or(|last_run| {
+                    dirty_parents.contains(&entity)
+                        || volatile.iter().any(|&id| entity_ref.contains_id(id))
+                        || changed_since(&entity_ref, &ignored, last_run, this_run)
+                        || hierarchy.children(&entity_ref).iter().any(|&child| {
+                            world.get_entity(child).is_ok_and(|child| {
+                                child
+                                    .get_change_ticks_by_id(score)
+                                    .is_some_and(|ticks| ticks.is_changed(last_run, this_run))
+                            })
+                        })
+                });
+                if !dirty {
+                    continue;
+                }
+
+                let weight_changed = last_run.is_some_and(|last_run| {
+                    entity_ref
+                        .get_change_ticks_by_id(weighted)
+                        .is_some_and(|ticks| ticks.is_changed(last_run, this_run))
+                });
+                let parent = hierarchy.parent(&entity_ref);
+                let before = entity_ref.get::<Score>().copied();
+
+                if entity_ref.contains::<Targets>() {
+                    Targets::score(world, entity);
+                } else {
+                    world.trigger_targets(OnScore, entity);
+                    world.trigger_targets(OnScorePostProcess, entity);
+                    world.flush();
+                }
+
+                let changed = weight_changed || world.get::<Score>(entity).copied() != before;
+                if let Some(parent) = parent.filter(|_| changed) {
+                    dirty_parents.insert(parent);
+                }
+            }
+
+            // Changes made while scoring happened at or before this tick, so they don't dirty the next run
+            let last_run = world.increment_change_tick();
+            if let Ok(mut root) = world.get_entity_mut(root) {
+                if fresh {
+                    root.insert(ScoringOrder(order));
+                }
+                root.insert(LastScored(last_run));
+            }
+        }
+    }
+}
+
+/// Returns whether any component of the entity, except the ignored ones, changed since the last run.
+fn changed_since(entity: &EntityRef, ignored: &[ComponentId], last_run: Tick, this_run: Tick) -> bool {
+    entity
+        .archetype()
+        .components()
+        .filter(|id| !ignored.contains(id))
+        .any(|id| {
+            entity
+                .get_change_ticks_by_id(id)
+                .is_some_and(|ticks| ticks.is_changed(last_run, this_run))
+        })
+}
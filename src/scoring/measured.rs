@@ -1,9 +1,21 @@
+use std::{any::Any, sync::Arc};
+
 use bevy::{
-    ecs::component::{ComponentHooks, Mutable, StorageType},
     prelude::*,
+    reflect::{
+        FromType, TypeRegistry,
+        serde::{
+            DeserializeWithRegistry, ReflectDeserializeWithRegistry, ReflectSerializeWithRegistry,
+            SerializeWithRegistry,
+        },
+    },
 };
+use serde::{Deserializer, Serializer, de::Error};
 
-use crate::{ecs::CommandsExt, event::OnScore, scoring::Score};
+use crate::{
+    event::OnScore,
+    scoring::{ParallelScorer, Score, ScoreTree, Scorer, deserialize_registered, serialize_registered},
+};
 
 /// [`Score`] [`Component`] that scores based on a [`Measure`] of its child [`Score`] + [`Weighted`] entities.
 /// Child entities without a [`Weighted`] component are considered fully weighted (1.0).
@@ -39,9 +51,18 @@ use crate::{ecs::CommandsExt, event::OnScore, scoring::Score};
 /// # world.flush();
 /// # assert_relative_eq!(world.get::<Score>(scorer).unwrap().get(), 0.89);
 /// ```
+///
+/// # Reflection
+///
+/// [`Measured`] is reflected as a struct holding its [`Measure`], which can be replaced through reflection.
+/// It's serialized as its [`Measure`], which must be registered with `#[reflect(Measure)]`
+/// like the provided measures are. Measures that aren't registered, like closures, can't be serialized.
+#[derive(Reflect, Scorer, Clone)]
+#[reflect(Component, Clone, SerializeWithRegistry, DeserializeWithRegistry)]
+#[scorer(observer = Self::observer)]
 pub struct Measured {
     /// The function that calculates the score.
-    measure: Box<dyn Measure>,
+    measure: Arc<dyn Measure>,
 }
 
 impl Measured {
@@ -49,7 +70,7 @@ impl Measured {
     #[must_use]
     pub fn new(measure: impl Measure) -> Self {
         Self {
-            measure: Box::new(measure),
+            measure: Arc::new(measure),
         }
     }
 
@@ -67,19 +88,25 @@ impl Measured {
 
     /// Sets the [`Measure`] used for scoring.
     pub fn set_measure(&mut self, measure: impl Measure) {
-        self.measure = Box::new(measure);
+        self.measure = Arc::new(measure);
     }
 
     /// [`Observer`] for [`Measured`] [`Score`] entities that scores based on all child [`Score`] entities.
     fn observer(
         trigger: Trigger<OnScore>,
-        target: Query<(&Children, &Measured)>,
+        target: Query<&Measured>,
+        tree: ScoreTree,
         mut scores: Query<(&mut Score, Option<&Weighted>)>,
     ) {
-        let Ok((children, settings)) = target.get(trigger.target()) else {
+        let Ok(settings) = target.get(trigger.target()) else {
             // The entity is not scoring for measured.
             return;
         };
+        let children = tree.children(trigger.target());
+        if children.is_empty() {
+            // The entity has nothing to measure.
+            return;
+        }
 
         let mut inputs = Vec::new();
 
@@ -98,20 +125,37 @@ impl Measured {
     }
 }
 
-impl Component for Measured {
-    type Mutability = Mutable;
-    const STORAGE_TYPE: StorageType = StorageType::Table;
+impl ParallelScorer for Measured {
+    fn score(&self, children: &[(Score, Weighted)]) -> Option<Score> {
+        Some(self.calculate(children.iter().map(|(score, weight)| (score, weight)).collect()))
+    }
+}
 
-    fn register_component_hooks(hooks: &mut ComponentHooks) {
-        hooks.on_add(|mut world, _ctx| {
-            #[derive(Resource, Default)]
-            struct MeasuredObserverSpawned;
+impl SerializeWithRegistry for Measured {
+    fn serialize<S: Serializer>(&self, serializer: S, registry: &TypeRegistry) -> Result<S::Ok, S::Error> {
+        let measure = self.measure.as_ref();
+        let reflected = registry
+            .get_type_data::<ReflectMeasure>((measure as &dyn Any).type_id())
+            .and_then(|reflect_measure| reflect_measure.as_reflect(measure));
+        serialize_registered(reflected, "Measure", serializer, registry)
+    }
+}
 
-            world
-                .commands()
-                .once::<MeasuredObserverSpawned>()
-                .observe(Self::observer);
-        });
+impl<'de> DeserializeWithRegistry<'de> for Measured {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D, registry: &TypeRegistry) -> Result<Self, D::Error> {
+        let (value, registration) = deserialize_registered(deserializer, registry)?;
+        let measure = registration
+            .data::<ReflectMeasure>()
+            .and_then(|reflect_measure| reflect_measure.get_boxed(value).ok())
+            .ok_or_else(|| {
+                D::Error::custom(format_args!(
+                    "`{}` is not a measure, is it registered with `#[reflect(Measure)]`?",
+                    registration.type_info().type_path()
+                ))
+            })?;
+        Ok(Self {
+            measure: Arc::from(measure),
+        })
     }
 }
 
@@ -163,12 +207,79 @@ impl Weighted {
 }
 
 /// A measure of scoring.
-#[reflect_trait]
-pub trait Measure: Send + Sync + 'static {
+pub trait Measure: Any + Send + Sync {
     /// Calculates the output score based on the input scores and weights.
     fn calculate(&self, inputs: Vec<(&Score, &Weighted)>) -> Score;
 }
 
+impl TypePath for dyn Measure {
+    fn type_path() -> &'static str {
+        "dyn bevy_observed_utility::scoring::Measure"
+    }
+
+    fn short_type_path() -> &'static str {
+        "dyn Measure"
+    }
+}
+
+/// A boxed [`Measure`], or the reflected value that isn't one.
+type BoxedMeasure = Result<Box<dyn Measure>, Box<dyn Reflect>>;
+
+/// Type data for [`Measure`]s, registered with `#[reflect(Measure)]`.
+///
+/// This works like [`ReflectEvaluator`](crate::scoring::ReflectEvaluator) does for evaluators.
+#[derive(Clone)]
+pub struct ReflectMeasure {
+    get_func: fn(&dyn Reflect) -> Option<&dyn Measure>,
+    get_mut_func: fn(&mut dyn Reflect) -> Option<&mut dyn Measure>,
+    get_boxed_func: fn(Box<dyn Reflect>) -> BoxedMeasure,
+    as_reflect_func: fn(&dyn Measure) -> Option<&dyn Reflect>,
+}
+
+impl ReflectMeasure {
+    /// Downcasts a reflected value to a [`Measure`], if it's of the registered type.
+    #[must_use]
+    pub fn get<'a>(&self, reflect_value: &'a dyn Reflect) -> Option<&'a dyn Measure> {
+        (self.get_func)(reflect_value)
+    }
+
+    /// Downcasts a mutable reflected value to a [`Measure`], if it's of the registered type.
+    #[must_use]
+    pub fn get_mut<'a>(&self, reflect_value: &'a mut dyn Reflect) -> Option<&'a mut dyn Measure> {
+        (self.get_mut_func)(reflect_value)
+    }
+
+    /// Downcasts a boxed reflected value to a [`Measure`], if it's of the registered type.
+    ///
+    /// # Errors
+    ///
+    /// Returns the value back if it's of another type.
+    pub fn get_boxed(&self, reflect_value: Box<dyn Reflect>) -> Result<Box<dyn Measure>, Box<dyn Reflect>> {
+        (self.get_boxed_func)(reflect_value)
+    }
+
+    /// Casts a [`Measure`] back to a reflected value, if it's of the registered type.
+    #[must_use]
+    pub fn as_reflect<'a>(&self, measure: &'a dyn Measure) -> Option<&'a dyn Reflect> {
+        (self.as_reflect_func)(measure)
+    }
+}
+
+impl<T: Measure + Reflect> FromType<T> for ReflectMeasure {
+    fn from_type() -> Self {
+        Self {
+            get_func: |reflect_value| reflect_value.downcast_ref::<T>().map(|value| value as &dyn Measure),
+            get_mut_func: |reflect_value| reflect_value.downcast_mut::<T>().map(|value| value as &mut dyn Measure),
+            get_boxed_func: |reflect_value| reflect_value.downcast::<T>().map(|value| value as Box<dyn Measure>),
+            as_reflect_func: |measure| {
+                (measure as &dyn Any)
+                    .downcast_ref::<T>()
+                    .map(|value| value as &dyn Reflect)
+            },
+        }
+    }
+}
+
 /// [`Measure`] that calculates the sum of the weighted input scores.
 #[derive(Reflect, Clone, Copy, PartialEq, Debug)]
 #[reflect(Measure, PartialEq, Debug)]
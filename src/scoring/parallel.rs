@@ -0,0 +1,261 @@
+use bevy::{
+    ecs::{component::ComponentId, world::EntityRef},
+    prelude::*,
+    tasks::{ComputeTaskPool, ParallelSlice, TaskPool},
+};
+
+use crate::scoring::{
+    AllOrNothing, Cooldown, Evaluated, FixedScore, Measured, PostEvaluated, Product, Score, ScoreHistory, ScoringOrder,
+    Sum, Targets, Weighted, Winning, post_order, score_children, score_in_order,
+};
+
+/// [`Score`] [`Component`] that can calculate its score from its already scored children without an [`Observer`],
+/// allowing independent scoring trees to be scored in parallel.
+///
+/// See [`ScoringPlugin::parallel`](crate::scoring::ScoringPlugin::parallel) for more information.
+pub trait ParallelScorer: Component {
+    /// Calculates the score based on the child [`Score`] entities and their [`Weighted`] weights.
+    /// Children without a [`Weighted`] component are considered fully weighted (1.0).
+    ///
+    /// Returning `None` leaves the current score unchanged.
+    fn score(&self, children: &[(Score, Weighted)]) -> Option<Score>;
+}
+
+/// Post-processing counterpart of [`ParallelScorer`], for [`Component`]s that adjust an entity's freshly calculated
+/// [`Score`] in [`OnScorePostProcess`].
+///
+/// [`OnScorePostProcess`]: crate::event::OnScorePostProcess
+pub trait ParallelPostProcessor: Component {
+    /// Adjusts the freshly calculated score.
+    fn post_process(&self, score: Score) -> Score;
+}
+
+/// Marker [`Component`] for the roots of scoring trees that are scored in parallel with
+/// [`ScoringPlugin::parallel`](crate::scoring::ScoringPlugin::parallel).
+///
+/// **[`OnScore`] and [`OnScorePostProcess`] are NOT triggered for the [`Score`] entities of these trees**,
+/// so observers listening to them won't run. Trees without this marker are scored one entity at a time as usual.
+///
+/// [`OnScore`]: crate::event::OnScore
+/// [`OnScorePostProcess`]: crate::event::OnScorePostProcess
+#[derive(Component, Reflect)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[reflect(Component, PartialEq, Debug, Default)]
+pub struct SkipScoringEvents;
+
+type ScoreFn = fn(&EntityRef, &[(Score, Weighted)]) -> Option<Score>;
+type PostProcessFn = fn(&EntityRef, Score) -> Score;
+
+/// [`Resource`] holding the [`ParallelScorer`]s and [`ParallelPostProcessor`]s known to parallel scoring.
+///
+/// All of the library-provided scorers (except [`RandomScore`]) and post-processors are registered by default,
+/// and [`Cooldown`], [`ScoreHistory`] and [`Targets`] are registered as
+/// [serial-only](ParallelScorers::register_serial_only).
+/// [`ActionPlugin`] registers [`FailurePenalty`] as serial-only too.
+///
+/// [`RandomScore`]: crate::scoring::RandomScore
+/// [`ActionPlugin`]: crate::acting::ActionPlugin
+/// [`FailurePenalty`]: crate::acting::FailurePenalty
+#[derive(Resource)]
+pub struct ParallelScorers {
+    scorers: Vec<(ComponentId, ScoreFn)>,
+    post_processors: Vec<(ComponentId, PostProcessFn)>,
+    serial_only: Vec<ComponentId>,
+}
+
+impl FromWorld for ParallelScorers {
+    fn from_world(world: &mut World) -> Self {
+        let mut this = Self {
+            scorers: Vec::new(),
+            post_processors: Vec::new(),
+            serial_only: Vec::new(),
+        };
+        this.add_scorer::<AllOrNothing>(world);
+        this.add_scorer::<Evaluated>(world);
+        this.add_scorer::<FixedScore>(world);
+        this.add_scorer::<Measured>(world);
+        this.add_scorer::<Product>(world);
+        this.add_scorer::<Sum>(world);
+        this.add_scorer::<Winning>(world);
+        this.add_post_processor::<PostEvaluated>(world);
+        this.add_serial_only::<Cooldown>(world);
+        this.add_serial_only::<ScoreHistory>(world);
+        this.add_serial_only::<Targets>(world);
+        this
+    }
+}
+
+impl ParallelScorers {
+    /// Registers the [`ParallelScorer`] `T`, so that trees using it can be scored in parallel.
+    pub fn register_scorer<T: ParallelScorer>(world: &mut World) {
+        let mut this = world
+            .remove_resource::<Self>()
+            .unwrap_or_else(|| Self::from_world(world));
+        this.add_scorer::<T>(world);
+        world.insert_resource(this);
+    }
+
+    /// Registers the [`ParallelPostProcessor`] `T`, so that trees using it can be scored in parallel.
+    pub fn register_post_processor<T: ParallelPostProcessor>(world: &mut World) {
+        let mut this = world
+            .remove_resource::<Self>()
+            .unwrap_or_else(|| Self::from_world(world));
+        this.add_post_processor::<T>(world);
+        world.insert_resource(this);
+    }
+
+    /// Registers the [`Component`] `T` as requiring its [`Observer`]s,
+    /// so that trees with [`Score`] entities using it are always scored one entity at a time.
+    pub fn register_serial_only<T: Component>(world: &mut World) {
+        let mut this = world
+            .remove_resource::<Self>()
+            .unwrap_or_else(|| Self::from_world(world));
+        this.add_serial_only::<T>(world);
+        world.insert_resource(this);
+    }
+
+    fn add_scorer<T: ParallelScorer>(&mut self, world: &mut World) {
+        fn score<T: ParallelScorer>(entity: &EntityRef, children: &[(Score, Weighted)]) -> Option<Score> {
+            entity.get::<T>().and_then(|scorer| scorer.score(children))
+        }
+
+        let id = world.register_component::<T>();
+        if !self.scorers.iter().any(|(existing, _)| *existing == id) {
+            self.scorers.push((id, score::<T>));
+        }
+    }
+
+    fn add_post_processor<T: ParallelPostProcessor>(&mut self, world: &mut World) {
+        fn post_process<T: ParallelPostProcessor>(entity: &EntityRef, score: Score) -> Score {
+            entity
+                .get::<T>()
+                .map_or(score, |processor| processor.post_process(score))
+        }
+
+        let id = world.register_component::<T>();
+        if !self.post_processors.iter().any(|(existing, _)| *existing == id) {
+            self.post_processors.push((id, post_process::<T>));
+        }
+    }
+
+    fn add_serial_only<T: Component>(&mut self, world: &mut World) {
+        let id = world.register_component::<T>();
+        if !self.serial_only.contains(&id) {
+            self.serial_only.push(id);
+        }
+    }
+
+    /// Scores the given root entities, scoring independent roots in parallel.
+    ///
+    /// Roots without [`SkipScoringEvents`], or whose trees contain a [`Score`] entity without a registered
+    /// [`ParallelScorer`], are instead scored by triggering [`OnScore`] and [`OnScorePostProcess`] in post-order,
+    /// one entity at a time.
+    ///
+    /// [`OnScore`]: crate::event::OnScore
+    /// [`OnScorePostProcess`]: crate::event::OnScorePostProcess
+    pub fn score(world: &mut World, roots: &[Entity]) {
+        let results = {
+            let world: &World = world;
+            let this = world.resource::<Self>();
+            let task_pool = ComputeTaskPool::get_or_init(TaskPool::default);
+            let chunk_size = roots.len().div_ceil(task_pool.thread_num()).max(1);
+
+            roots.par_chunk_map(task_pool, chunk_size, |_, roots| {
+                roots
+                    .iter()
+                    .map(|&root| this.score_tree(world, root))
+                    .collect::<Vec<_>>()
+            })
+        };
+
+        for result in results.into_iter().flatten() {
+            match result {
+                TreeScores::Scored(scores) => {
+                    for (entity, new_score) in scores {
+                        if let Some(mut score) = world.get_mut::<Score>(entity) {
+                            *score = new_score;
+                        }
+                    }
+                }
+                TreeScores::Fallback(order) => score_in_order(world, &order),
+            }
+        }
+    }
+
+    /// Scores a single tree without mutating the world, falling back if any entity can't be scored in parallel.
+    fn score_tree(&self, world: &World, root: Entity) -> TreeScores {
+        if !world
+            .get_entity(root)
+            .is_ok_and(|root| root.contains::<SkipScoringEvents>())
+        {
+            return fallback(world, root);
+        }
+
+        let mut results = Vec::new();
+        let mut inputs = Vec::new();
+        // The scores and weights of the already scored children of the entities on the stack, in order.
+        let mut values: Vec<Option<(Score, Weighted)>> = Vec::new();
+        let mut stack = vec![(root, score_children(world, root), 0)];
+
+        while let Some((entity, children, next_child)) = stack.last_mut() {
+            if let Some(&child) = children.get(*next_child) {
+                *next_child += 1;
+                stack.push((child, score_children(world, child), 0));
+                continue;
+            }
+
+            let (entity, num_children) = (*entity, *next_child);
+            stack.pop();
+            let first_child = values.len() - num_children;
+
+            let value = match world.get_entity(entity) {
+                Ok(entity_ref) if entity_ref.contains::<Score>() => {
+                    let scorer = self.scorers.iter().find(|(id, _)| entity_ref.contains_id(*id));
+                    let serial_only = self.serial_only.iter().any(|id| entity_ref.contains_id(*id));
+                    let (Some((_, score)), false) = (scorer, serial_only) else {
+                        // The scorer needs its observer to run.
+                        return fallback(world, root);
+                    };
+
+                    inputs.clear();
+                    inputs.extend(values[first_child..].iter().flatten().copied());
+
+                    let current = entity_ref.get::<Score>().copied().unwrap_or_default();
+                    let mut new_score = score(&entity_ref, &inputs).unwrap_or(current);
+                    for (id, post_process) in &self.post_processors {
+                        if entity_ref.contains_id(*id) {
+                            new_score = post_process(&entity_ref, new_score);
+                        }
+                    }
+
+                    results.push((entity, new_score));
+                    let weight = entity_ref.get::<Weighted>().copied().unwrap_or(Weighted::MAX);
+                    Some((new_score, weight))
+                }
+                // Not a score entity, nothing to calculate.
+                _ => None,
+            };
+
+            values.truncate(first_child);
+            values.push(value);
+        }
+
+        TreeScores::Scored(results)
+    }
+}
+
+/// Returns the post-order of the tree rooted at the given entity, to be scored with observers instead.
+fn fallback(world: &World, root: Entity) -> TreeScores {
+    let order = world
+        .get::<ScoringOrder>(root)
+        .map_or_else(|| post_order(world, root), |order| order.entities().to_vec());
+    TreeScores::Fallback(order)
+}
+
+/// The outcome of scoring a single tree in parallel.
+enum TreeScores {
+    /// The new scores, to be written back to the world.
+    Scored(Vec<(Entity, Score)>),
+    /// The post-order of the tree, which has to be scored with observers instead.
+    Fallback(Vec<Entity>),
+}
@@ -1,9 +1,6 @@
-use bevy::{
-    ecs::component::{ComponentHooks, Mutable, StorageType},
-    prelude::*,
-};
+use bevy::prelude::*;
 
-use crate::{ecs::CommandsExt, event::OnScore, scoring::Score};
+use crate::scoring::{ChildAggregateScorer, Score, Scorer};
 
 /// [`Score`] [`Component`] that scores the product of all child [`Score`] entities.
 ///
@@ -30,8 +27,9 @@ use crate::{ecs::CommandsExt, event::OnScore, scoring::Score};
 /// # world.flush();
 /// # assert_relative_eq!(world.get::<Score>(scorer).unwrap().get(), 0.21);
 /// ```
-#[derive(Reflect, Clone, Copy, PartialEq, Debug, Default)]
+#[derive(Reflect, Scorer, Clone, Copy, PartialEq, Debug, Default)]
 #[reflect(Component, PartialEq, Debug, Default)]
+#[scorer(aggregate)]
 pub struct Product {
     /// The threshold for the product of child scores to be considered a success.
     threshold: Score,
@@ -67,53 +65,33 @@ impl Product {
         self.threshold = threshold.into();
     }
 
-    /// [`Observer`] for [`Product`] [`Score`] entities that scores based on all child [`Score`] entities.
-    fn observer(trigger: Trigger<OnScore>, target: Query<(&Children, &Product)>, mut scores: Query<&mut Score>) {
-        let Ok((children, settings)) = target.get(trigger.target()) else {
-            // The entity is not scoring for product.
-            return;
-        };
-
+    /// Calculates the product of the given child scores.
+    #[must_use]
+    pub fn calculate(&self, child_scores: impl IntoIterator<Item = Score>) -> Score {
         let mut product: f32 = 1.;
         let mut num_scores = 0;
 
-        for child_score in scores.iter_many(children) {
+        for child_score in child_scores {
             product *= child_score.get();
             num_scores += 1;
         }
 
-        if settings.use_compensation && num_scores > 0 {
+        if self.use_compensation && num_scores > 0 {
             let mod_factor = 1. - 1. / (num_scores as f32);
             let makeup = (1. - product) * mod_factor;
             product += makeup * product;
         }
 
-        if product < settings.threshold().get() {
+        if product < self.threshold().get() {
             product = 0.;
         }
 
-        let Ok(mut actor_score) = scores.get_mut(trigger.target()) else {
-            // The entity is not scoring.
-            return;
-        };
-
-        actor_score.set(product);
+        Score::new(product)
     }
 }
 
-impl Component for Product {
-    type Mutability = Mutable;
-    const STORAGE_TYPE: StorageType = StorageType::Table;
-
-    fn register_component_hooks(hooks: &mut ComponentHooks) {
-        hooks.on_add(|mut world, _ctx| {
-            #[derive(Resource, Default)]
-            struct ProductObserverSpawned;
-
-            world
-                .commands()
-                .once::<ProductObserverSpawned>()
-                .observe(Self::observer);
-        });
+impl ChildAggregateScorer for Product {
+    fn aggregate(&self, children: impl Iterator<Item = Score>) -> Score {
+        self.calculate(children)
     }
 }
@@ -1,15 +1,11 @@
 use std::ops::RangeBounds;
 
-use bevy::{
-    ecs::component::{ComponentHooks, StorageType},
-    prelude::*,
-};
+use bevy::prelude::*;
 use rand::{Rng, RngCore};
 
 use crate::{
-    ecs::CommandsExt,
     event::OnScore,
-    scoring::{Score, ScoreRange},
+    scoring::{Score, ScoreRange, Scorer},
 };
 
 /// [`Score`] [`Component`] that scores a random value within a range.
@@ -27,11 +23,13 @@ use crate::{
 /// # let mut commands = world.commands();
 /// # let scorer =
 /// commands
-///     .spawn((RandomScore::new(StdRng::from_entropy()), Score::default()))
+///     .spawn((RandomScore::new(StdRng::from_os_rng()), Score::default()))
 /// #   .id();
 /// # commands.trigger_targets(RunScoring, scorer);
 /// # world.flush();
 /// ```
+#[derive(Scorer)]
+#[scorer(observer = Self::observer)]
 pub struct RandomScore {
     /// The random number generator to use.
     pub rng: Box<dyn RngCore + Send + Sync + 'static>,
@@ -70,31 +68,15 @@ impl RandomScore {
     }
 
     fn observer(trigger: Trigger<OnScore>, mut target: Query<(&mut Score, &mut RandomScore)>) {
-        let Ok((mut actor_score, mut settings)) = target.get_mut(trigger.entity()) else {
+        let Ok((mut actor_score, mut settings)) = target.get_mut(trigger.target()) else {
             // The entity is not scoring for random.
             return;
         };
 
         // TODO: We're assuming the range is inclusive, but it might not be.
         let range = settings.range.min_f32()..=settings.range.max_f32();
-        let value = settings.rng_mut().gen_range(range);
+        let value = settings.rng_mut().random_range(range);
 
         actor_score.set(value);
     }
 }
-
-impl Component for RandomScore {
-    const STORAGE_TYPE: StorageType = StorageType::Table;
-
-    fn register_component_hooks(hooks: &mut ComponentHooks) {
-        hooks.on_add(|mut world, _entity, _component| {
-            #[derive(Resource, Default)]
-            struct RandomScoreObserverSpawned;
-
-            world
-                .commands()
-                .once::<RandomScoreObserverSpawned>()
-                .observe(Self::observer);
-        });
-    }
-}
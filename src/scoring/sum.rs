@@ -1,9 +1,6 @@
-use bevy::{
-    ecs::component::{ComponentHooks, Mutable, StorageType},
-    prelude::*,
-};
+use bevy::prelude::*;
 
-use crate::{ecs::CommandsExt, event::OnScore, scoring::Score};
+use crate::scoring::{ChildAggregateScorer, Score, Scorer};
 
 /// [`Score`] [`Component`] that scores based on the sum of its child [`Score`] entities.
 ///
@@ -29,8 +26,9 @@ use crate::{ecs::CommandsExt, event::OnScore, scoring::Score};
 /// # world.flush();
 /// # assert_eq!(world.get::<Score>(scorer).unwrap().get(), 1.0);
 /// ```
-#[derive(Reflect, Clone, Copy, PartialEq, Debug, Default)]
+#[derive(Reflect, Scorer, Clone, Copy, PartialEq, Debug, Default)]
 #[reflect(Component, PartialEq, Debug, Default)]
+#[scorer(aggregate)]
 pub struct Sum {
     /// The threshold for the sum of child scores to be considered a success.
     threshold: Score,
@@ -56,42 +54,25 @@ impl Sum {
         self.threshold = threshold.into();
     }
 
-    /// [`Observer`] for [`Sum`] [`Score`] entities that scores based on all child [`Score`] entities.
-    fn observer(trigger: Trigger<OnScore>, target: Query<(&Children, &Sum)>, mut scores: Query<&mut Score>) {
-        let Ok((children, settings)) = target.get(trigger.target()) else {
-            // The entity is not scoring for sum.
-            return;
-        };
-
+    /// Calculates the sum of the given child scores.
+    #[must_use]
+    pub fn calculate(&self, child_scores: impl IntoIterator<Item = Score>) -> Score {
         let mut sum: f32 = 0.;
 
-        for child_score in scores.iter_many(children) {
+        for child_score in child_scores {
             sum += child_score.get();
         }
 
-        if sum < settings.threshold().get() {
+        if sum < self.threshold().get() {
             sum = 0.;
         }
 
-        let Ok(mut actor_score) = scores.get_mut(trigger.target()) else {
-            // The entity is not scoring.
-            return;
-        };
-
-        actor_score.set(sum);
+        Score::new(sum)
     }
 }
 
-impl Component for Sum {
-    type Mutability = Mutable;
-    const STORAGE_TYPE: StorageType = StorageType::Table;
-
-    fn register_component_hooks(hooks: &mut ComponentHooks) {
-        hooks.on_add(|mut world, _ctx| {
-            #[derive(Resource, Default)]
-            struct SumObserverSpawned;
-
-            world.commands().once::<SumObserverSpawned>().observe(Self::observer);
-        });
+impl ChildAggregateScorer for Sum {
+    fn aggregate(&self, children: impl Iterator<Item = Score>) -> Score {
+        self.calculate(children)
     }
 }
@@ -0,0 +1,239 @@
+use bevy::prelude::*;
+
+use crate::{
+    ecs::AncestorQuery,
+    event::{OnScore, OnScorePostProcess},
+    picking::Picker,
+    scoring::{Score, ScoreHierarchy, ScoringOrder, post_order, score_in_order},
+};
+
+/// [`Component`] for [`Score`] entities that are scored once per candidate target entity,
+/// e.g. to pick which enemy to attack.
+///
+/// The entity and its [`Score`] children are scored once for each target, in order.
+/// While a target is scored, it is available with [`Targets::current`], which scoring [`Observer`]s
+/// can find with an [`AncestorQuery`]. See [`score_target`] for a helper that does this.
+///
+/// Afterwards, the entity's [`Score`] is the highest score among all targets, and the target that scored it
+/// is [`Targets::best`]. The entity and its children are then scored once more for the best target,
+/// which is the only pass that updates stateful post-processors, see [`ComparingTargets`]. If the entity is a choice of its parent's [`Picker`], the best target is also recorded
+/// in [`Picker::best_targets`], so that picking it picks the target along with the action.
+///
+/// The candidates can be updated at any time, e.g. from a [`Query`] in a system that runs before scoring.
+/// Without candidates, the entity scores [`Score::MIN`].
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_observed_utility::prelude::*;
+///
+/// # let mut app = App::new();
+/// # app.add_plugins(ObservedUtilityPlugins::TurnBased);
+/// /// This goes on the target entities.
+/// #[derive(Component)]
+/// struct Health(f32);
+///
+/// /// The weaker the target, the higher the score.
+/// impl From<&Health> for Score {
+///     fn from(health: &Health) -> Self {
+///         Score::new(1. - health.0 / 100.)
+///     }
+/// }
+///
+/// /// This goes on the score entity.
+/// #[derive(Component)]
+/// struct Weakest;
+///
+/// app.add_observer(score_target::<Health, Weakest>);
+///
+/// # let mut world = app.world_mut();
+/// let enemies = [world.spawn(Health(80.)).id(), world.spawn(Health(30.)).id()];
+/// let scorer = world
+///     .spawn((Weakest, Score::default(), Targets::new(enemies)))
+///     .id();
+///
+/// world.trigger_targets(RunScoring, scorer);
+/// # world.flush();
+/// assert_eq!(Some(enemies[1]), world.get::<Targets>(scorer).unwrap().best());
+/// # assert_eq!(0.7, world.get::<Score>(scorer).unwrap().get());
+/// ```
+#[derive(Component, Reflect)]
+#[derive(Clone, PartialEq, Debug, Default)]
+#[reflect(Component, PartialEq, Debug, Default)]
+#[component(on_add = ScoringOrder::on_score_changed, on_remove = ScoringOrder::on_score_changed)]
+pub struct Targets {
+    /// The candidate target entities.
+    candidates: Vec<Entity>,
+    /// The target entity currently being scored, if any.
+    current: Option<Entity>,
+    /// The scores of the candidates from the last scoring run, in order.
+    scores: Vec<(Entity, Score)>,
+    /// The highest scoring target entity from the last scoring run, if any.
+    best: Option<Entity>,
+}
+
+impl Targets {
+    /// Creates a new [`Targets`] with the given candidate target entities.
+    #[must_use]
+    pub fn new(candidates: impl IntoIterator<Item = Entity>) -> Self {
+        Self {
+            candidates: candidates.into_iter().collect(),
+            ..default()
+        }
+    }
+
+    /// Returns the candidate target entities.
+    #[must_use]
+    pub fn candidates(&self) -> &[Entity] {
+        &self.candidates
+    }
+
+    /// Sets the candidate target entities.
+    pub fn set_candidates(&mut self, candidates: impl IntoIterator<Item = Entity>) {
+        self.candidates.clear();
+        self.candidates.extend(candidates);
+    }
+
+    /// Returns the target entity currently being scored, or `None` outside of scoring.
+    #[must_use]
+    pub fn current(&self) -> Option<Entity> {
+        self.current
+    }
+
+    /// Returns the score of each candidate from the last scoring run, in order.
+    #[must_use]
+    pub fn scores(&self) -> &[(Entity, Score)] {
+        &self.scores
+    }
+
+    /// Returns the highest scoring target entity from the last scoring run, if any.
+    /// Ties are won by the first candidate.
+    #[must_use]
+    pub fn best(&self) -> Option<Entity> {
+        self.best
+    }
+
+    /// Scores the given [`Targets`] entity and its [`Score`] children once per candidate target entity.
+    ///
+    /// This is run by [`ScoringPlugin`](crate::scoring::ScoringPlugin) when the entity is reached while scoring.
+    pub fn score(world: &mut World, entity: Entity) {
+        let Some(candidates) = world.get::<Targets>(entity).map(|targets| targets.candidates.clone()) else {
+            return;
+        };
+
+        // The children are scored in the usual order, followed by the entity itself
+        let mut order = Vec::new();
+        let hierarchy = ScoreHierarchy::of(world);
+        let children = world
+            .get_entity(entity)
+            .map_or(&[][..], |entity| hierarchy.children(&entity))
+            .to_vec();
+        for child in children {
+            order.extend(post_order(world, child));
+        }
+
+        // Nested Targets entities are compared within the outer comparison
+        let nested = world.contains_resource::<ComparingTargets>();
+        world.insert_resource(ComparingTargets);
+        let mut scores = Vec::with_capacity(candidates.len());
+        for target in candidates {
+            Self::score_for(world, entity, &order, target);
+            scores.push((target, world.get::<Score>(entity).copied().unwrap_or_default()));
+        }
+        if !nested {
+            world.remove_resource::<ComparingTargets>();
+        }
+
+        let best = scores
+            .iter()
+            .fold(None, |best: Option<(Entity, Score)>, &(target, score)| match best {
+                Some((_, best_score)) if best_score >= score => best,
+                _ => Some((target, score)),
+            });
+        if let Some((target, _)) = best {
+            // Update the stateful post-processors once, for the best target
+            Self::score_for(world, entity, &order, target);
+        }
+
+        if let Some(mut score) = world.get_mut::<Score>(entity) {
+            *score = best.map_or(Score::MIN, |(_, score)| score);
+        }
+        if let Some(mut targets) = world.get_mut::<Targets>(entity) {
+            targets.current = None;
+            targets.scores = scores;
+            targets.best = best.map(|(target, _)| target);
+        }
+
+        // Record the best target for picking
+        let Some(parent) = world
+            .get_entity(entity)
+            .ok()
+            .and_then(|entity| hierarchy.parent(&entity))
+        else {
+            return;
+        };
+        if let Some(mut picker) = world.get_mut::<Picker>(parent) {
+            match best {
+                Some((target, _)) => picker.best_targets.insert(entity, target),
+                None => picker.best_targets.remove(&entity),
+            };
+        }
+    }
+
+    /// Scores the given entity and its children in the given order, for the given target.
+    fn score_for(world: &mut World, entity: Entity, order: &[Entity], target: Entity) {
+        if let Some(mut targets) = world.get_mut::<Targets>(entity) {
+            targets.current = Some(target);
+        }
+
+        score_in_order(world, order);
+        world.trigger_targets(OnScore, entity);
+        world.trigger_targets(OnScorePostProcess, entity);
+        world.flush();
+    }
+}
+
+/// [`Resource`] present while [`Targets`] entities compare their candidates,
+/// i.e. while they score themselves and their children once per candidate target.
+///
+/// Afterwards, they are scored once more for the best target without it.
+/// [`OnScorePostProcess`] observers that keep state across scoring runs, like [`Cooldown`] counting down
+/// or [`ScoreHistory`] recording samples, should still adjust the [`Score`] while this is present,
+/// but leave their state untouched, so that it's updated once per scoring run.
+///
+/// [`Cooldown`]: crate::scoring::Cooldown
+/// [`ScoreHistory`]: crate::scoring::ScoreHistory
+#[derive(Resource)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct ComparingTargets;
+
+/// [`Observer`] helper function that calculates the score of a [`Score`] entity marked with `ScoreMarker`
+/// based on the [`Component`] `T` on the target entity currently being scored by the closest [`Targets`] entity,
+/// which may be the score entity itself.
+///
+/// The [`Component`] `T` must implement [`Into<Score>`] for its reference type `&T`.
+/// If there is no current target, or it doesn't have the component, the score is set to the minimum.
+///
+/// See [`Targets`] for an example.
+pub fn score_target<T: Component, ScoreMarker: Component>(
+    trigger: Trigger<OnScore>,
+    mut scores: Query<&mut Score, With<ScoreMarker>>,
+    mut targets: AncestorQuery<&'static Targets>,
+    components: Query<&T>,
+) where
+    for<'a> &'a T: Into<Score>,
+{
+    let scorer = trigger.target();
+    let Ok(mut score) = scores.get_mut(scorer) else {
+        return;
+    };
+
+    let current = targets.get(scorer).ok().and_then(Targets::current);
+    if let Some(component) = current.and_then(|target| components.get(target).ok()) {
+        *score = component.into();
+    } else {
+        // If there is no target, set the score to the minimum.
+        *score = Score::MIN;
+    }
+}
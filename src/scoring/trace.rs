@@ -0,0 +1,310 @@
+use std::fmt;
+
+use bevy::{
+    ecs::{
+        component::{ComponentHooks, ComponentId, Mutable, StorageType},
+        world::DeferredWorld,
+    },
+    prelude::*,
+};
+use serde::Serialize;
+
+use crate::{
+    ecs::CommandsExt,
+    event::OnPicked,
+    picking::Picker,
+    scoring::{Score, ScoreHierarchy, ScoreOf, Scorers, ScoringOrder, Targets, Weighted, score_children},
+};
+
+/// [`Component`] for actor entities that records why they picked what they picked (requires `trace` feature).
+///
+/// Every time the actor's [`Picker`] triggers [`OnPicked`], the trace is replaced with a snapshot of the
+/// actor's [`Score`] children and their descendants, as they were scored in the last scoring run,
+/// along with the picker's decision.
+///
+/// The trace can be pretty-printed as an indented tree with [`Display`](fmt::Display),
+/// or exported as JSON with [`ScoringTrace::to_json`].
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_observed_utility::prelude::*;
+///
+/// # let mut app = App::new();
+/// # app.add_plugins(ObservedUtilityPlugins::TurnBased);
+/// # let mut world = app.world_mut();
+/// #[derive(Component)]
+/// pub struct Drink;
+/// #[derive(Component)]
+/// pub struct Idle;
+///
+/// let drink = world.register_component::<Drink>();
+/// let idle = world.register_component::<Idle>();
+///
+/// let thirst = world.spawn((FixedScore::new(0.8), Score::default())).id();
+/// let actor = world
+///     .spawn((Picker::new(idle).with(thirst, drink), Highest, ScoringTrace::default()))
+///     .add_child(thirst)
+///     .id();
+/// # world.flush();
+///
+/// world.trigger(RunScoring);
+/// # world.flush();
+/// world.trigger(RunPicking);
+/// # world.flush();
+///
+/// let trace = world.get::<ScoringTrace>(actor).unwrap();
+/// assert!(trace.picked().is_some_and(|picked| picked.action.ends_with("Drink")));
+/// assert_eq!(0.8, trace.nodes()[0].score);
+/// println!("{trace}");
+/// ```
+#[derive(Reflect, Serialize)]
+#[derive(Clone, PartialEq, Debug, Default)]
+#[reflect(Component, PartialEq, Debug, Default)]
+pub struct ScoringTrace {
+    /// The traced [`Score`] children of the actor entity.
+    nodes: Vec<TraceNode>,
+    /// The picker's decision, if it has picked yet.
+    picked: Option<TracePick>,
+}
+
+impl ScoringTrace {
+    /// Returns the traced [`Score`] children of the actor entity, each holding its own children.
+    #[must_use]
+    pub fn nodes(&self) -> &[TraceNode] {
+        &self.nodes
+    }
+
+    /// Returns the picker's decision, or `None` if it hasn't picked yet.
+    #[must_use]
+    pub fn picked(&self) -> Option<&TracePick> {
+        self.picked.as_ref()
+    }
+
+    /// Exports the trace as pretty-printed JSON.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("traces only hold JSON-compatible values")
+    }
+
+    /// Records the trace of the given actor entity, using its [`Picker`] and the picked action and target.
+    #[must_use]
+    pub fn record(world: &World, actor: Entity, action: ComponentId, target: Option<Entity>) -> Self {
+        let picker = world.get::<Picker>(actor);
+        let nodes = world
+            .get_entity(actor)
+            .map_or(&[][..], |actor| ScoreHierarchy::of(world).children(&actor))
+            .iter()
+            .filter(|&&child| world.get::<Score>(child).is_some())
+            .map(|&child| {
+                let mut node = TraceNode::record(world, child);
+                node.action = picker
+                    .and_then(|picker| picker.choices.get(&child))
+                    .map(|&action| component_name(world, action));
+                node
+            })
+            .collect();
+
+        Self {
+            nodes,
+            picked: Some(TracePick {
+                action: component_name(world, action),
+                target,
+                default: picker.is_some_and(|picker| picker.is_default(action)),
+            }),
+        }
+    }
+
+    /// [`Observer`] that records the trace of the actor entity when it picks an action.
+    fn on_picked(trigger: Trigger<OnPicked>, mut world: DeferredWorld) {
+        let actor = trigger.target();
+        if world.get::<ScoringTrace>(actor).is_none() {
+            // The actor isn't traced.
+            return;
+        }
+
+        let OnPicked { action, target } = *trigger.event();
+        let trace = Self::record(&world, actor, action, target);
+        if let Some(mut current) = world.get_mut::<ScoringTrace>(actor) {
+            *current = trace;
+        }
+    }
+}
+
+impl Component for ScoringTrace {
+    type Mutability = Mutable;
+    const STORAGE_TYPE: StorageType = StorageType::Table;
+
+    fn register_component_hooks(hooks: &mut ComponentHooks) {
+        hooks.on_add(|mut world, _ctx| {
+            #[derive(Resource, Default)]
+            struct ScoringTraceObserverSpawned;
+
+            world
+                .commands()
+                .once::<ScoringTraceObserverSpawned>()
+                .observe(Self::on_picked);
+        });
+    }
+}
+
+impl fmt::Display for ScoringTrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.picked {
+            Some(picked) => writeln!(f, "{picked}")?,
+            None => writeln!(f, "nothing picked")?,
+        }
+        for node in &self.nodes {
+            node.fmt_indented(f, 1)?;
+        }
+        Ok(())
+    }
+}
+
+/// A traced [`Score`] entity in a [`ScoringTrace`].
+#[derive(Reflect, Serialize)]
+#[derive(Clone, PartialEq, Debug)]
+#[reflect(PartialEq, Debug, no_field_bounds)]
+pub struct TraceNode {
+    /// The [`Score`] entity.
+    pub entity: Entity,
+    /// The action mapped to this entity in the actor's [`Picker`], if any.
+    pub action: Option<String>,
+    /// The names of the entity's other components, like its scorer and markers.
+    pub kind: Vec<String>,
+    /// The entity's [`Weighted`] weight, if any, used as an input by its parent.
+    pub weight: Option<f32>,
+    /// The entity's [`Score`], i.e. the output of its scorer.
+    pub score: f32,
+    /// The score of each candidate target, if the entity is scored per target with [`Targets`].
+    pub targets: Vec<(Entity, f32)>,
+    /// The entity's [`Score`] children, whose scores and weights are the inputs of its scorer.
+    pub children: Vec<TraceNode>,
+}
+
+impl TraceNode {
+    /// Records the given [`Score`] entity and its [`Score`] descendants.
+    #[must_use]
+    pub fn record(world: &World, entity: Entity) -> Self {
+        // Bookkeeping components that don't describe how the entity is scored.
+        let ignored = [
+            world.component_id::<Score>(),
+            world.component_id::<Weighted>(),
+            world.component_id::<ScoringOrder>(),
+            world.component_id::<ChildOf>(),
+            world.component_id::<Children>(),
+            world.component_id::<ScoreOf>(),
+            world.component_id::<Scorers>(),
+            world.component_id::<Name>(),
+        ];
+        let kind = world
+            .inspect_entity(entity)
+            .into_iter()
+            .flatten()
+            .filter(|info| !ignored.contains(&Some(info.id())))
+            .map(|info| component_name(world, info.id()))
+            .collect();
+        let targets = world
+            .get::<Targets>(entity)
+            .map(|targets| {
+                targets
+                    .scores()
+                    .iter()
+                    .map(|(target, score)| (*target, score.get()))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let children = score_children(world, entity)
+            .iter()
+            .filter(|&&child| world.get::<Score>(child).is_some())
+            .map(|&child| Self::record(world, child))
+            .collect();
+
+        Self {
+            entity,
+            action: None,
+            kind,
+            weight: world.get::<Weighted>(entity).map(|weighted| weighted.get().get()),
+            score: world.get::<Score>(entity).map_or(0., Score::get),
+            targets,
+            children,
+        }
+    }
+
+    /// Returns the inputs of the entity's scorer, i.e. the score and weight of each child.
+    /// Children without a [`Weighted`] component are considered fully weighted (1.0).
+    pub fn inputs(&self) -> impl Iterator<Item = (f32, f32)> + '_ {
+        self.children
+            .iter()
+            .map(|child| (child.score, child.weight.unwrap_or(1.)))
+    }
+
+    fn fmt_indented(&self, f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+        write!(
+            f,
+            "{:indent$}{} {}: {:.3}",
+            "",
+            self.entity,
+            self.kind.join(" + "),
+            self.score,
+            indent = depth * 2
+        )?;
+        if let Some(weight) = self.weight {
+            write!(f, " (weight {weight:.3})")?;
+        }
+        if let Some(action) = &self.action {
+            write!(f, " -> {action}")?;
+        }
+        writeln!(f)?;
+        for (target, score) in &self.targets {
+            writeln!(f, "{:indent$}target {target}: {score:.3}", "", indent = depth * 2 + 2)?;
+        }
+        for child in &self.children {
+            child.fmt_indented(f, depth + 1)?;
+        }
+        Ok(())
+    }
+}
+
+/// The decision of the [`Picker`] traced in a [`ScoringTrace`].
+#[derive(Reflect, Serialize)]
+#[derive(Clone, PartialEq, Debug)]
+#[reflect(PartialEq, Debug)]
+pub struct TracePick {
+    /// The name of the picked action.
+    pub action: String,
+    /// The picked target entity, if any.
+    pub target: Option<Entity>,
+    /// Whether the picked action is the picker's default action.
+    pub default: bool,
+}
+
+impl fmt::Display for TracePick {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "picked {}", self.action)?;
+        if let Some(target) = self.target {
+            write!(f, " targeting {target}")?;
+        }
+        if self.default {
+            write!(f, " (default)")?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the short type path of the component if it's registered, or its full name otherwise.
+fn component_name(world: &World, id: ComponentId) -> String {
+    let Some(info) = world.components().get_info(id) else {
+        return format!("{id:?}");
+    };
+    info.type_id()
+        .zip(world.get_resource::<AppTypeRegistry>())
+        .and_then(|(type_id, registry)| {
+            registry
+                .read()
+                .get(type_id)
+                .map(|registration| registration.type_info().type_path_table().short_path().to_string())
+        })
+        .unwrap_or_else(|| info.name().to_string())
+}
@@ -1,9 +1,6 @@
-use bevy::{
-    ecs::component::{ComponentHooks, Mutable, StorageType},
-    prelude::*,
-};
+use bevy::prelude::*;
 
-use crate::{ecs::CommandsExt, event::OnScore, scoring::Score};
+use crate::scoring::{ChildAggregateScorer, Score, Scorer};
 
 /// [`Score`] [`Component`] that scores based on the maximum of its child [`Score`] entities.
 ///
@@ -29,8 +26,9 @@ use crate::{ecs::CommandsExt, event::OnScore, scoring::Score};
 /// # world.flush();
 /// # assert_eq!(world.get::<Score>(scorer).unwrap().get(), 0.7);
 /// ```
-#[derive(Reflect, Clone, Copy, PartialEq, Debug, Default)]
+#[derive(Reflect, Scorer, Clone, Copy, PartialEq, Debug, Default)]
 #[reflect(Component, PartialEq, Debug, Default)]
+#[scorer(aggregate)]
 pub struct Winning {
     /// The threshold for the maximum of child scores to be considered a success.
     threshold: Score,
@@ -56,46 +54,26 @@ impl Winning {
         self.threshold = threshold;
     }
 
-    /// [`Observer`] for [`Winning`] [`Score`] entities that scores based on all child [`Score`] entities.
-    fn observer(trigger: Trigger<OnScore>, actor: Query<(&Children, &Winning)>, mut scores: Query<&mut Score>) {
-        let Ok((children, settings)) = actor.get(trigger.target()) else {
-            // The entity is not scoring for winning.
-            return;
-        };
-
+    /// Calculates the maximum of the given child scores.
+    #[must_use]
+    pub fn calculate(&self, child_scores: impl IntoIterator<Item = Score>) -> Score {
         let mut max: f32 = 0.;
 
-        for child_score in scores.iter_many(children) {
+        for child_score in child_scores {
             if child_score.get() > max {
                 max = child_score.get();
             }
         }
-        if max < settings.threshold().get() {
+        if max < self.threshold().get() {
             max = 0.;
         }
 
-        let Ok(mut actor_score) = scores.get_mut(trigger.target()) else {
-            // The entity is not scoring.
-            return;
-        };
-
-        actor_score.set(max);
+        Score::new(max)
     }
 }
 
-impl Component for Winning {
-    type Mutability = Mutable;
-    const STORAGE_TYPE: StorageType = StorageType::Table;
-
-    fn register_component_hooks(hooks: &mut ComponentHooks) {
-        hooks.on_add(|mut world, _ctx| {
-            #[derive(Resource, Default)]
-            struct WinningObserverSpawned;
-
-            world
-                .commands()
-                .once::<WinningObserverSpawned>()
-                .observe(Self::observer);
-        });
+impl ChildAggregateScorer for Winning {
+    fn aggregate(&self, children: impl Iterator<Item = Score>) -> Score {
+        self.calculate(children)
     }
 }